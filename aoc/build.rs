@@ -0,0 +1,5 @@
+fn main() {
+    // `days!` finds the days from the files in `src/`, so adding one has to
+    // rebuild the crate.
+    println!("cargo:rerun-if-changed=src");
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// The answer produced by a part, kept separate from its text output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Parses an answer as written by hand, e.g. in an `.expected` file.
+    pub fn parse(s: &str) -> Self {
+        s.trim()
+            .parse()
+            .map(Answer::Integer)
+            .unwrap_or_else(|_| Answer::Text(s.trim().to_owned()))
+    }
+
+    /// Compares answers by their textual rendition, so that `Integer(7)` matches `Text("7")`.
+    pub fn matches(&self, other: &Answer) -> bool {
+        let lines = |a: &Answer| {
+            a.to_string()
+                .lines()
+                .map(|l| l.trim_end().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        lines(self) == lines(other)
+    }
+
+    /// A single-line rendition of the answer, suitable for table cells.
+    pub fn summary(&self) -> String {
+        match self {
+            Answer::Grid(rows) => format!(
+                "<grid {}x{}>",
+                rows.iter().map(|r| r.chars().count()).max().unwrap_or(0),
+                rows.len()
+            ),
+            a => a.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Answer::Grid(rows)
+    }
+}
+
+/// Reads the expected answer for a part from the sidecar file of an input.
+///
+/// For `inputs/day1/small.txt`, the sidecar is `inputs/day1/small.expected` and
+/// holds one `<part>: <answer>` line per part. Repeating a part spreads its
+/// answer over several lines, which is how grid answers are written down.
+pub fn expected_from_sidecar(input: &str, part: usize) -> Option<Answer> {
+    let sidecar = Path::new(input).with_extension("expected");
+    let content = fs::read_to_string(sidecar).ok()?;
+
+    let lines = content
+        .lines()
+        .filter_map(|l| l.split_once(':'))
+        .filter(|(p, _)| p.trim().parse() == Ok(part))
+        .map(|(_, a)| a.strip_prefix(' ').unwrap_or(a).to_owned())
+        .collect::<Vec<_>>();
+
+    match lines.len() {
+        0 => None,
+        1 => Some(Answer::parse(&lines[0])),
+        _ => Some(Answer::Grid(lines)),
+    }
+}
+
+/// How an answer compares to the expected one for its input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Pass,
+    Fail(Answer),
+    Unknown,
+}
+
+impl Verdict {
+    pub fn new(answer: &Answer, expected: Option<Answer>) -> Self {
+        match expected {
+            Some(e) if answer.matches(&e) => Verdict::Pass,
+            Some(e) => Verdict::Fail(e),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail(e) => write!(f, "fail (expected {})", e.summary()),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// The result of a successful run of a part.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Solution {
+    pub answer: Answer,
+    pub verdict: Verdict,
+    pub parse: Duration,
+    pub duration: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::{temp_dir, TempDir};
+
+    /// Writes an input and its sidecar, returning the path of the input along
+    /// with its directory.
+    fn input(expected: &str) -> (TempDir, String) {
+        let dir = temp_dir("answer");
+        fs::write(dir.join("small.txt"), "").unwrap();
+        fs::write(dir.join("small.expected"), expected).unwrap();
+        let input = dir.join("small.txt").to_string_lossy().into_owned();
+        (dir, input)
+    }
+
+    #[test]
+    fn sidecars_hold_a_line_per_part() {
+        let (_dir, input) = input("1: 7\n2: five\n");
+
+        assert_eq!(expected_from_sidecar(&input, 1), Some(Answer::Integer(7)));
+        assert_eq!(
+            expected_from_sidecar(&input, 2),
+            Some(Answer::Text(String::from("five")))
+        );
+    }
+
+    #[test]
+    fn sidecars_can_lack_a_part() {
+        let (_dir, input) = input("1: 7\n");
+        assert_eq!(expected_from_sidecar(&input, 2), None);
+
+        let dir = temp_dir("answer");
+        let lone = dir.join("lone.txt").to_string_lossy().into_owned();
+        assert_eq!(expected_from_sidecar(&lone, 1), None);
+    }
+
+    #[test]
+    fn sidecars_allow_whitespace() {
+        let (_dir, input) = input(" 1 :   42  \r\n\n2:\tword \n");
+
+        assert_eq!(expected_from_sidecar(&input, 1), Some(Answer::Integer(42)));
+        assert_eq!(
+            expected_from_sidecar(&input, 2),
+            Some(Answer::Text(String::from("word")))
+        );
+    }
+
+    #[test]
+    fn repeated_parts_are_grids() {
+        let (_dir, input) = input("1: 3\n2: #..\n2:  #.\n");
+
+        assert_eq!(
+            expected_from_sidecar(&input, 2),
+            Some(Answer::Grid(vec![String::from("#.."), String::from(" #.")]))
+        );
+    }
+
+    #[test]
+    fn numbers_parse_as_integers() {
+        assert_eq!(Answer::parse(" 150 "), Answer::Integer(150));
+        assert_eq!(Answer::parse("-3"), Answer::Integer(-3));
+        assert_eq!(Answer::parse("1.5"), Answer::Text(String::from("1.5")));
+        assert_eq!(Answer::parse(" a b\n"), Answer::Text(String::from("a b")));
+    }
+
+    #[test]
+    fn answers_match_by_their_text() {
+        let seven = Answer::Integer(7);
+
+        assert!(seven.matches(&Answer::Text(String::from("7"))));
+        assert!(!seven.matches(&Answer::Text(String::from("07"))));
+        assert!(!seven.matches(&Answer::Integer(8)));
+
+        let grid = Answer::Grid(vec![String::from("#. "), String::from(".#")]);
+        assert!(grid.matches(&Answer::Text(String::from("#.\n.#"))));
+        assert!(!grid.matches(&Answer::Text(String::from("#.\n#."))));
+    }
+
+    #[test]
+    fn verdicts_compare_with_the_expected_answer() {
+        let answer = Answer::Integer(198);
+
+        assert_eq!(
+            Verdict::new(&answer, Some(Answer::Text(String::from("198")))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            Verdict::new(&answer, Some(Answer::Integer(230))),
+            Verdict::Fail(Answer::Integer(230))
+        );
+        assert_eq!(Verdict::new(&answer, None), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use color_eyre::eyre::{self, WrapErr};
+use color_eyre::Help;
+use serde::{Deserialize, Serialize};
+
+use crate::bench::format_nanos;
+use crate::fetch::FetchConfig;
+use crate::history::{current_commit, RunKey};
+
+/// Directory of the baselines in the data directory.
+pub const BASELINES: &str = "baselines";
+pub const DEFAULT_BASELINE: &str = "baseline";
+
+/// Slowdown, as a fraction of the baseline, beyond which a timing counts as
+/// a regression.
+pub const DEFAULT_THRESHOLD: f64 = 0.1;
+
+/// The median solve time of a part on an input, as kept in a baseline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timing {
+    pub year: u16,
+    pub day: usize,
+    pub part: usize,
+    pub variant: String,
+    pub input: String,
+    pub commit: Option<String>,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn key(&self) -> RunKey {
+        (
+            self.year,
+            self.day,
+            self.part,
+            self.variant.clone(),
+            self.input.clone(),
+        )
+    }
+}
+
+/// How a timing compares to its baseline.
+#[derive(Debug, Clone, Copy)]
+pub struct Comparison {
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// Relative change from the baseline; positive when slower.
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+
+        (self.current.as_secs_f64() - baseline) / baseline
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} ({:+.1}%)",
+            format_nanos(self.baseline.as_nanos() as f64),
+            format_nanos(self.current.as_nanos() as f64),
+            self.change() * 100.0
+        )
+    }
+}
+
+/// Timings saved under a name, to compare later runs against.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    timings: HashMap<RunKey, Timing>,
+}
+
+impl Baseline {
+    pub fn path(config: &FetchConfig, name: &str) -> PathBuf {
+        config
+            .data_dir
+            .join(BASELINES)
+            .join(format!("{}.jsonl", name))
+    }
+
+    pub fn load(config: &FetchConfig, name: &str) -> eyre::Result<Self> {
+        let path = Self::path(config, name);
+        if !path.exists() {
+            return Err(
+                eyre::eyre!("There is no baseline named {}", name).suggestion(format!(
+                    "Save one with `aoc bench --save-baseline {}`",
+                    name
+                )),
+            );
+        }
+
+        let file = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+
+        let timings = file
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Timing>(l).ok())
+            .map(|t| (t.key(), t))
+            .collect();
+
+        Ok(Self { timings })
+    }
+
+    /// Loads a baseline, or starts an empty one if it doesn't exist yet.
+    pub fn load_or_default(config: &FetchConfig, name: &str) -> eyre::Result<Self> {
+        if Self::path(config, name).exists() {
+            Self::load(config, name)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, config: &FetchConfig, name: &str) -> eyre::Result<()> {
+        let path = Self::path(config, name);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+        }
+
+        let mut timings = self.timings.values().collect::<Vec<_>>();
+        timings.sort_by_key(|t| t.key());
+
+        let lines = timings
+            .into_iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        fs::write(&path, lines.join("\n") + "\n")
+            .wrap_err_with(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Sets the timing of a part, stamped with the current commit.
+    pub fn insert(&mut self, key: RunKey, solve: Duration) {
+        let (year, day, part, variant, input) = key.clone();
+
+        self.timings.insert(
+            key,
+            Timing {
+                year,
+                day,
+                part,
+                variant,
+                input,
+                commit: current_commit(),
+                solve,
+            },
+        );
+    }
+
+    pub fn compare(&self, key: &RunKey, current: Duration) -> Option<Comparison> {
+        self.timings.get(key).map(|t| Comparison {
+            baseline: t.solve,
+            current,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::{config, install_handler, temp_dir};
+
+    fn key(part: usize) -> RunKey {
+        (2021, 1, part, "main".to_string(), "input.txt".to_string())
+    }
+
+    fn comparison(baseline: u64, current: u64) -> Comparison {
+        Comparison {
+            baseline: Duration::from_millis(baseline),
+            current: Duration::from_millis(current),
+        }
+    }
+
+    #[test]
+    fn baselines_are_saved_and_loaded() {
+        let dir = temp_dir("baseline");
+        let config = config(&dir, None);
+
+        let mut baseline = Baseline::default();
+        baseline.insert(key(1), Duration::from_millis(10));
+        baseline.insert(key(2), Duration::from_millis(20));
+        baseline.save(&config, "saved").unwrap();
+
+        let loaded = Baseline::load(&config, "saved").unwrap();
+        let c = loaded.compare(&key(2), Duration::from_millis(30)).unwrap();
+        assert_eq!(c.baseline, Duration::from_millis(20));
+        assert_eq!(c.current, Duration::from_millis(30));
+        assert!(loaded.compare(&key(3), Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn missing_baselines_fail_to_load() {
+        install_handler();
+
+        let dir = temp_dir("baseline");
+        let config = config(&dir, None);
+
+        let error = format!("{:?}", Baseline::load(&config, "missing").unwrap_err());
+        assert!(error.contains("There is no baseline named missing"));
+        assert!(error.contains("Save one with `aoc bench --save-baseline missing`"));
+
+        let empty = Baseline::load_or_default(&config, "missing").unwrap();
+        assert!(empty.compare(&key(1), Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn regressions_are_beyond_the_threshold() {
+        assert!((comparison(100, 150).change() - 0.5).abs() < 1e-9);
+        assert!((comparison(100, 50).change() + 0.5).abs() < 1e-9);
+
+        assert!(comparison(100, 111).is_regression(0.1));
+        assert!(!comparison(100, 109).is_regression(0.1));
+        assert!(!comparison(100, 150).is_regression(0.6));
+        assert!(!comparison(100, 50).is_regression(0.1));
+    }
+
+    #[test]
+    fn zero_baselines_never_regress() {
+        let c = comparison(0, 100);
+        assert_eq!(c.change(), 0.0);
+        assert!(!c.is_regression(0.1));
+    }
+}
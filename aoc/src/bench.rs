@@ -0,0 +1,226 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Answer;
+
+/// How long a benchmark runs for. It stops as soon as either limit is reached.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub iterations: Option<usize>,
+    pub budget: Option<Duration>,
+}
+
+impl BenchConfig {
+    pub fn is_done(&self, runs: usize, elapsed: Duration) -> bool {
+        let iterations_done = self.iterations.map(|n| runs >= n).unwrap_or(false);
+        let budget_done = self.budget.map(|b| elapsed >= b).unwrap_or(false);
+
+        let unbounded = self.iterations.is_none() && self.budget.is_none();
+
+        runs > 0 && (iterations_done || budget_done || unbounded)
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: None,
+            budget: Some(Duration::from_secs(2)),
+        }
+    }
+}
+
+/// Summary statistics over a set of samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut samples = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = samples.len();
+        if n == 0 {
+            return Self {
+                samples: 0,
+                min: 0.0,
+                median: 0.0,
+                mean: 0.0,
+                p95: 0.0,
+                std_dev: 0.0,
+            };
+        }
+
+        let median = if n % 2 == 0 {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+        let p95 = samples[((n as f64 * 0.95).ceil() as usize).clamp(1, n) - 1];
+
+        Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean,
+            p95,
+            std_dev: variance.sqrt(),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {} | median {} | mean {} | p95 {} | σ {} ({} runs)",
+            format_nanos(self.min),
+            format_nanos(self.median),
+            format_nanos(self.mean),
+            format_nanos(self.p95),
+            format_nanos(self.std_dev),
+            self.samples
+        )
+    }
+}
+
+/// Timings of a benchmarked part; loading and parsing the input are measured
+/// apart from solving it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub answer: Answer,
+    pub load: Stats,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+pub fn format_nanos(ns: f64) -> String {
+    if ns < 1e3 {
+        format!("{:.0} ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.3} µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.3} ms", ns / 1e6)
+    } else {
+        format!("{:.3} s", ns / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nanos(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn stats_of_samples() {
+        let stats = Stats::new(&nanos(&[40, 10, 30, 20]));
+
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 25.0);
+        assert_eq!(stats.mean, 25.0);
+        // Nearest rank: the ceil(0.95 * 4) = 4th sample.
+        assert_eq!(stats.p95, 40.0);
+        // Population deviation: sqrt((225 + 25 + 25 + 225) / 4).
+        assert!((stats.std_dev - 125f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn p95_is_a_nearest_rank() {
+        let samples = (1..=100).collect::<Vec<_>>();
+        assert_eq!(Stats::new(&nanos(&samples)).p95, 95.0);
+
+        let samples = (1..=21).collect::<Vec<_>>();
+        assert_eq!(Stats::new(&nanos(&samples)).p95, 20.0);
+    }
+
+    #[test]
+    fn stats_of_a_single_sample() {
+        let stats = Stats::new(&nanos(&[7]));
+
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.min, 7.0);
+        assert_eq!(stats.median, 7.0);
+        assert_eq!(stats.mean, 7.0);
+        assert_eq!(stats.p95, 7.0);
+        assert_eq!(stats.std_dev, 0.0);
+    }
+
+    #[test]
+    fn stats_without_samples() {
+        let stats = Stats::new(&[]);
+
+        assert_eq!(stats.samples, 0);
+        assert_eq!(stats.median, 0.0);
+    }
+
+    #[test]
+    fn benches_stop_at_the_iterations() {
+        let config = BenchConfig {
+            warmup: 0,
+            iterations: Some(3),
+            budget: None,
+        };
+
+        assert!(!config.is_done(2, Duration::from_secs(100)));
+        assert!(config.is_done(3, Duration::ZERO));
+    }
+
+    #[test]
+    fn benches_stop_at_the_budget() {
+        let config = BenchConfig {
+            warmup: 0,
+            iterations: None,
+            budget: Some(Duration::from_secs(2)),
+        };
+
+        assert!(!config.is_done(1000, Duration::from_secs(1)));
+        assert!(config.is_done(1, Duration::from_secs(2)));
+        // At least one run is made, however long it took.
+        assert!(!config.is_done(0, Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn benches_stop_at_the_first_limit() {
+        let config = BenchConfig {
+            warmup: 0,
+            iterations: Some(10),
+            budget: Some(Duration::from_secs(2)),
+        };
+
+        assert!(config.is_done(10, Duration::from_millis(1)));
+        assert!(config.is_done(1, Duration::from_secs(3)));
+        assert!(!config.is_done(5, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn unbounded_benches_run_once() {
+        let config = BenchConfig {
+            warmup: 0,
+            iterations: None,
+            budget: None,
+        };
+
+        assert!(!config.is_done(0, Duration::ZERO));
+        assert!(config.is_done(1, Duration::ZERO));
+    }
+}
@@ -0,0 +1,865 @@
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use color_eyre::eyre::{self, WrapErr};
+
+use crate::baseline::{Baseline, DEFAULT_THRESHOLD};
+use crate::bench::BenchConfig;
+use crate::crosscheck::cross_check;
+use crate::fetch::{FetchConfig, InputManager};
+use crate::history::{History, Run};
+use crate::report::Report;
+use crate::submit::{SubmitOutcome, Submitter};
+use crate::ui::{Options, Resume};
+use crate::{
+    format_duration, get_day, Answer, CancelToken, Context, DynDay, Solution, Verdict, MAIN_VARIANT,
+};
+
+type BoxedDay = Box<dyn DynDay + Send + Sync>;
+
+fn selection_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("day")
+            .long("day")
+            .short("d")
+            .takes_value(true)
+            .value_name("DAY")
+            .required_unless("all")
+            .conflicts_with("all")
+            .help("Day to run"),
+        Arg::with_name("part")
+            .long("part")
+            .short("p")
+            .takes_value(true)
+            .value_name("PART")
+            .possible_values(&["1", "2"])
+            .help("Part to run; both parts are run if omitted"),
+        Arg::with_name("input")
+            .long("input")
+            .short("i")
+            .takes_value(true)
+            .value_name("FILE")
+            .conflicts_with("all")
+            .help("Input file; every input of the day is run if omitted"),
+        Arg::with_name("all")
+            .long("all")
+            .short("a")
+            .help("Runs every implemented day"),
+    ]
+}
+
+fn variant_arg() -> Arg<'static, 'static> {
+    Arg::with_name("variant")
+        .long("variant")
+        .short("v")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Solver to use for the parts [default: main]")
+}
+
+pub fn app() -> App<'static, 'static> {
+    App::new("aoc")
+        .about("Advent of Code solutions")
+        .setting(AppSettings::VersionlessSubcommands)
+        .arg(
+            Arg::with_name("timeout")
+                .long("timeout")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .help("Cancels runs started from the TUI after this long"),
+        )
+        .arg(
+            Arg::with_name("baseline")
+                .long("baseline")
+                .takes_value(true)
+                .value_name("NAME")
+                .help("Baseline the TUI compares solve times with [default: baseline]"),
+        )
+        .arg(
+            Arg::with_name("threshold")
+                .long("threshold")
+                .takes_value(true)
+                .value_name("PERCENT")
+                .help("Slowdown beyond which the TUI shows a solve time as a regression [default: 10]"),
+        )
+        .arg(
+            Arg::with_name("watch")
+                .long("watch")
+                .short("w")
+                .help("Runs instances again when their input changes, and restarts on a rebuild"),
+        )
+        .arg(
+            Arg::with_name("resume")
+                .long("resume")
+                .takes_value(true)
+                .value_name("SELECTION")
+                .env(RESUME_ENV)
+                .hidden(true),
+        )
+        .arg(
+            Arg::with_name("year")
+                .long("year")
+                .short("y")
+                .takes_value(true)
+                .value_name("YEAR")
+                .global(true)
+                .help("Year of the puzzles [default: the latest implemented one]"),
+        )
+        .arg(
+            Arg::with_name("session")
+                .long("session")
+                .takes_value(true)
+                .value_name("TOKEN")
+                .env("AOC_SESSION")
+                .hide_env_values(true)
+                .global(true)
+                .help("Session cookie used to download missing real inputs"),
+        )
+        .arg(
+            Arg::with_name("base-url")
+                .long("base-url")
+                .takes_value(true)
+                .value_name("URL")
+                .env("AOC_BASE_URL")
+                .global(true)
+                .help("Website inputs are downloaded from [default: https://adventofcode.com]"),
+        )
+        .subcommand(
+            SubCommand::with_name("run")
+                .about("Runs solutions without the TUI and prints the results")
+                .args(&selection_args())
+                .arg(variant_arg())
+                .arg(
+                    Arg::with_name("debug")
+                        .long("debug")
+                        .help("Prints the debug output of each part to stderr"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("bench")
+                .about("Runs solutions repeatedly and prints timing statistics")
+                .args(&selection_args())
+                .arg(variant_arg())
+                .arg(
+                    Arg::with_name("warmup")
+                        .long("warmup")
+                        .takes_value(true)
+                        .value_name("RUNS")
+                        .help("Number of untimed runs before measuring [default: 3]"),
+                )
+                .arg(
+                    Arg::with_name("runs")
+                        .long("runs")
+                        .short("n")
+                        .takes_value(true)
+                        .value_name("RUNS")
+                        .help("Number of timed runs"),
+                )
+                .arg(
+                    Arg::with_name("time")
+                        .long("time")
+                        .short("t")
+                        .takes_value(true)
+                        .value_name("SECONDS")
+                        .help("Time budget for the timed runs [default: 2]"),
+                )
+                .arg(
+                    Arg::with_name("save-baseline")
+                        .long("save-baseline")
+                        .takes_value(true)
+                        .value_name("NAME")
+                        .help("Saves the solve times as a baseline to compare against"),
+                )
+                .arg(
+                    Arg::with_name("compare")
+                        .long("compare")
+                        .takes_value(true)
+                        .value_name("NAME")
+                        .help("Compares the solve times with a saved baseline"),
+                )
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .takes_value(true)
+                        .value_name("PERCENT")
+                        .requires("compare")
+                        .help("Slowdown beyond which a solve time is a regression [default: 10]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("check")
+                .about("Runs every variant of the parts and reports when they disagree")
+                .args(&selection_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("report")
+                .about("Reports the latest results of every day as Markdown or JSON")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .help("Writes both reports, to PATH.md and PATH.json"),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .conflicts_with("output")
+                        .help("Prints the JSON report instead of the Markdown one"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fetch")
+                .about("Downloads the real inputs that aren't cached yet")
+                .arg(
+                    Arg::with_name("day")
+                        .long("day")
+                        .short("d")
+                        .takes_value(true)
+                        .value_name("DAY")
+                        .required_unless("all")
+                        .conflicts_with("all")
+                        .help("Day to fetch"),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .long("all")
+                        .short("a")
+                        .help("Fetches the inputs of every implemented day"),
+                )
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .short("f")
+                        .help("Downloads inputs again even if they are cached"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("submit")
+                .about("Submits an answer, unless past submissions already tell its outcome")
+                .arg(
+                    Arg::with_name("day")
+                        .long("day")
+                        .short("d")
+                        .takes_value(true)
+                        .value_name("DAY")
+                        .required(true)
+                        .help("Day of the answer"),
+                )
+                .arg(
+                    Arg::with_name("part")
+                        .long("part")
+                        .short("p")
+                        .takes_value(true)
+                        .value_name("PART")
+                        .possible_values(&["1", "2"])
+                        .required(true)
+                        .help("Part of the answer"),
+                )
+                .arg(
+                    Arg::with_name("answer")
+                        .value_name("ANSWER")
+                        .help("Answer to submit; the part is run on the real input if omitted"),
+                ),
+        )
+}
+
+/// Reads where inputs are downloaded from.
+pub fn fetch_config(matches: &ArgMatches) -> FetchConfig {
+    let mut config = FetchConfig {
+        session: matches.value_of("session").map(String::from),
+        ..FetchConfig::default()
+    };
+    if let Some(url) = matches.value_of("base-url") {
+        config.base_url = url.to_string();
+    }
+
+    config
+}
+
+/// Reads the slowdown, given in percent, beyond which a solve time counts as
+/// a regression.
+fn threshold(matches: &ArgMatches) -> eyre::Result<Option<f64>> {
+    match matches.value_of("threshold") {
+        Some(t) => Ok(Some(t.parse::<f64>()? / 100.0)),
+        None => Ok(None),
+    }
+}
+
+/// Reads the year of the puzzles.
+fn year(matches: &ArgMatches) -> eyre::Result<u16> {
+    match matches.value_of("year") {
+        Some(y) => Ok(y.parse()?),
+        None => Ok(crate::latest_year()),
+    }
+}
+
+/// The days, inputs and parts picked by the selection arguments.
+struct Selection {
+    year: u16,
+    days: Vec<(usize, BoxedDay, Vec<String>)>,
+    parts: Vec<usize>,
+    variant: String,
+}
+
+impl Selection {
+    fn from_matches(matches: &ArgMatches) -> eyre::Result<Self> {
+        let parts = match matches.value_of("part") {
+            Some(p) => vec![p.parse()?],
+            None => vec![1, 2],
+        };
+
+        let year = year(matches)?;
+
+        let days = if matches.is_present("all") {
+            crate::days(year)
+                .into_iter()
+                .filter_map(|(n, d)| d.map(|d| (n, d)))
+                .collect::<Vec<_>>()
+        } else {
+            let n = matches.value_of("day").unwrap().parse()?;
+            vec![(n, get_day(year, n)?)]
+        };
+
+        let days = days
+            .into_iter()
+            .map(|(n, day)| {
+                let inputs = match matches.value_of("input") {
+                    Some(i) => vec![i.to_string()],
+                    None => day.inputs().iter().map(|i| i.to_string()).collect(),
+                };
+                (n, day, inputs)
+            })
+            .collect();
+
+        let variant = matches
+            .value_of("variant")
+            .unwrap_or(MAIN_VARIANT)
+            .to_string();
+
+        Ok(Self {
+            year,
+            days,
+            parts,
+            variant,
+        })
+    }
+}
+
+/// Reads the TUI options from the top-level arguments.
+pub fn options(matches: &ArgMatches) -> eyre::Result<Options> {
+    let timeout = match matches.value_of("timeout") {
+        Some(t) => Some(Duration::from_secs_f64(t.parse()?)),
+        None => None,
+    };
+
+    Ok(Options {
+        timeout,
+        year: Some(year(matches)?),
+        fetch: fetch_config(matches),
+        baseline: matches.value_of("baseline").map(String::from),
+        threshold: threshold(matches)?,
+        watch: matches.is_present("watch"),
+        resume: matches.value_of("resume").map(str::parse).transpose()?,
+    })
+}
+
+/// Environment variable a restarted TUI reads its selection from.
+const RESUME_ENV: &str = "AOC_RESUME";
+
+/// Starts the TUI again from the rebuilt binary, with the same arguments, and
+/// picking up from the given selection.
+pub fn restart(resume: &Resume) -> eyre::Result<()> {
+    let mut args = std::env::args_os();
+    let program = args.next().ok_or_else(|| eyre::eyre!("No program name"))?;
+
+    let mut command = Command::new(program);
+    command.args(args).env(RESUME_ENV, resume.to_string());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        Err(command.exec()).wrap_err("Failed to restart")
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = command.status().wrap_err("Failed to restart")?;
+        std::process::exit(status.code().unwrap_or(1))
+    }
+}
+
+/// `Part N`, followed by the variant unless it is the main one.
+fn describe_part(part: usize, variant: &str) -> String {
+    if variant == MAIN_VARIANT {
+        format!("Part {}", part)
+    } else {
+        format!("Part {} ({})", part, variant)
+    }
+}
+
+/// Runs the `run` subcommand. Returns `false` if any of the parts failed or
+/// gave a wrong answer.
+pub fn run(matches: &ArgMatches) -> eyre::Result<bool> {
+    let selection = Selection::from_matches(matches)?;
+    let show_debug = matches.is_present("debug");
+    let config = fetch_config(matches);
+    let manager = InputManager::new(config.clone());
+    let history = History::new(&config);
+
+    let mut success = true;
+
+    for (number, day, inputs) in selection.days.iter() {
+        println!("Day {} - {}", number, day.title());
+
+        for input in inputs.iter() {
+            if let Err(e) = manager.ensure(input) {
+                println!("  [{}] could not fetch input", input);
+                eprintln!("{:?}", e);
+                success = false;
+                continue;
+            }
+
+            let parts = selection
+                .parts
+                .iter()
+                .map(|p| (*p, selection.variant.as_str()))
+                .collect::<Vec<_>>();
+
+            let record = |part, variant: &str, result: &eyre::Result<Solution>| {
+                let run = Run::new(selection.year, *number, part, variant, input, result);
+                if let Err(e) = history.record(run) {
+                    eprintln!("{:?}", e);
+                }
+            };
+
+            success &= run_input(day.as_ref(), input, &parts, show_debug, record);
+        }
+    }
+
+    Ok(success)
+}
+
+fn run_input(
+    day: &(dyn DynDay + Send + Sync),
+    input: &str,
+    parts: &[(usize, &str)],
+    show_debug: bool,
+    record: impl Fn(usize, &str, &eyre::Result<Solution>),
+) -> bool {
+    let (output_tx, output_rx) = mpsc::channel();
+    let (debug_tx, debug_rx) = mpsc::channel();
+
+    let results = day.run_parts(input, parts, &Context::new(output_tx, debug_tx));
+
+    if show_debug {
+        debug_rx.try_iter().for_each(|s| eprint!("{}", s));
+    }
+
+    let results = match results {
+        Ok(results) => results,
+        Err(e) => {
+            for &(part, variant) in parts {
+                record(part, variant, &Err(eyre::eyre!("{:#}", e)));
+            }
+
+            println!("  [{}] could not read input", input);
+            eprintln!("{:?}", e);
+            return false;
+        }
+    };
+
+    let mut success = true;
+
+    for (&(part, variant), result) in parts.iter().zip(results) {
+        record(part, variant, &result);
+        let part = describe_part(part, variant);
+
+        match result {
+            Ok(solution) => {
+                println!(
+                    "  {} [{}] {} (parse {}) {}",
+                    part,
+                    input,
+                    format_duration(&solution.duration),
+                    format_duration(&solution.parse),
+                    solution.verdict
+                );
+                solution
+                    .answer
+                    .to_string()
+                    .lines()
+                    .for_each(|l| println!("    {}", l));
+                success &= !matches!(solution.verdict, Verdict::Fail(_));
+            }
+            Err(e) => {
+                println!("  {} [{}] failed", part, input);
+                eprintln!("{:?}", e);
+                success = false;
+            }
+        }
+    }
+
+    output_rx
+        .try_iter()
+        .flat_map(|s| s.lines().map(String::from).collect::<Vec<_>>())
+        .for_each(|l| println!("    | {}", l));
+
+    success
+}
+
+/// Runs the `check` subcommand. Returns `false` if the variants of any part
+/// disagree, or if any of them failed.
+pub fn check(matches: &ArgMatches) -> eyre::Result<bool> {
+    let selection = Selection::from_matches(matches)?;
+    let manager = InputManager::new(fetch_config(matches));
+
+    let mut success = true;
+
+    for (number, day, inputs) in selection.days.iter() {
+        println!("Day {} - {}", number, day.title());
+
+        for input in inputs.iter() {
+            if let Err(e) = manager.ensure(input) {
+                println!("  [{}] could not fetch input", input);
+                eprintln!("{:?}", e);
+                success = false;
+                continue;
+            }
+
+            if !Path::new(input).exists() {
+                println!("  [{}] skipped: input file is missing", input);
+                continue;
+            }
+
+            for part in selection.parts.iter() {
+                let check = match cross_check(day.as_ref(), input, *part) {
+                    Ok(check) => check,
+                    Err(e) => {
+                        println!("  Part {} [{}] could not read input", part, input);
+                        eprintln!("{:?}", e);
+                        success = false;
+                        continue;
+                    }
+                };
+
+                let names = check
+                    .results
+                    .iter()
+                    .map(|(v, _)| *v)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                if let Some(answer) = check.answer() {
+                    println!(
+                        "  Part {} [{}] agree on {} ({})",
+                        part,
+                        input,
+                        answer.summary(),
+                        names
+                    );
+                    continue;
+                }
+
+                success = false;
+                println!("  Part {} [{}] DISAGREE", part, input);
+
+                let width = check
+                    .results
+                    .iter()
+                    .map(|(v, _)| v.len())
+                    .max()
+                    .unwrap_or(0);
+                for (variant, result) in check.results.iter() {
+                    let text = match result {
+                        Ok(answer) => answer.to_string(),
+                        Err(e) => format!("error: {}", e),
+                    };
+
+                    let mut lines = text.lines();
+                    println!(
+                        "    {:width$}  {}",
+                        variant,
+                        lines.next().unwrap_or_default(),
+                        width = width
+                    );
+                    lines.for_each(|l| println!("    {:width$}  {}", "", l, width = width));
+                }
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Runs the `bench` subcommand. Returns `false` if any part failed, or got
+/// slower than the baseline it is compared with.
+pub fn bench(matches: &ArgMatches) -> eyre::Result<bool> {
+    bench_with(matches, fetch_config(matches))
+}
+
+fn bench_with(matches: &ArgMatches, fetch: FetchConfig) -> eyre::Result<bool> {
+    let selection = Selection::from_matches(matches)?;
+    let manager = InputManager::new(fetch.clone());
+
+    let mut config = BenchConfig::default();
+    if let Some(w) = matches.value_of("warmup") {
+        config.warmup = w.parse()?;
+    }
+    if let Some(n) = matches.value_of("runs") {
+        config.iterations = Some(n.parse()?);
+        config.budget = None;
+    }
+    if let Some(t) = matches.value_of("time") {
+        config.budget = Some(Duration::from_secs_f64(t.parse()?));
+    }
+
+    let compare = match matches.value_of("compare") {
+        Some(name) => Some(Baseline::load(&fetch, name)?),
+        None => None,
+    };
+    let threshold = threshold(matches)?.unwrap_or(DEFAULT_THRESHOLD);
+    let save = matches.value_of("save-baseline");
+    let mut saved = match save {
+        Some(name) => Some(Baseline::load_or_default(&fetch, name)?),
+        None => None,
+    };
+
+    let mut success = true;
+    let mut regressions = 0;
+
+    for (number, day, inputs) in selection.days.iter() {
+        println!("Day {} - {}", number, day.title());
+
+        for input in inputs.iter() {
+            if let Err(e) = manager.ensure(input) {
+                println!("  [{}] could not fetch input", input);
+                eprintln!("{:?}", e);
+                success = false;
+                continue;
+            }
+
+            for part in selection.parts.iter() {
+                let variant = selection.variant.as_str();
+                let cancel = CancelToken::new();
+                let part_name = describe_part(*part, variant);
+
+                match day.bench(input, *part, variant, config, &cancel) {
+                    Ok(report) => {
+                        println!("  {} [{}] {}", part_name, input, report.answer.summary());
+                        println!("    load  {}", report.load);
+                        println!("    parse {}", report.parse);
+                        println!("    solve {}", report.solve);
+
+                        let key = (
+                            selection.year,
+                            *number,
+                            *part,
+                            variant.to_string(),
+                            input.to_string(),
+                        );
+                        let solve = Duration::from_nanos(report.solve.median as u64);
+
+                        if let Some(baseline) = &compare {
+                            match baseline.compare(&key, solve) {
+                                Some(c) if c.is_regression(threshold) => {
+                                    println!("    REGRESSION {}", c);
+                                    regressions += 1;
+                                }
+                                Some(c) => println!("    baseline {}", c),
+                                None => println!("    baseline: no timing"),
+                            }
+                        }
+
+                        if let Some(saved) = &mut saved {
+                            saved.insert(key, solve);
+                        }
+                    }
+                    Err(e) => {
+                        println!("  {} [{}] failed", part_name, input);
+                        eprintln!("{:?}", e);
+                        success = false;
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Some(name), Some(saved)) = (save, &saved) {
+        saved.save(&fetch, name)?;
+        println!("Saved baseline {}", name);
+    }
+
+    if regressions > 0 {
+        println!(
+            "{} regression(s) beyond {:.0}%",
+            regressions,
+            threshold * 100.0
+        );
+    }
+
+    Ok(success && regressions == 0)
+}
+
+/// Runs the `report` subcommand.
+pub fn report(matches: &ArgMatches) -> eyre::Result<()> {
+    let history = History::new(&fetch_config(matches));
+    let report = Report::new(year(matches)?, &history.latest()?);
+
+    if let Some(path) = matches.value_of("output") {
+        let (markdown, json) = report.write(Path::new(path))?;
+        println!("Wrote {} and {}", markdown.display(), json.display());
+    } else if matches.is_present("json") {
+        println!("{}", report.to_json()?);
+    } else {
+        print!("{}", report.to_markdown());
+    }
+
+    Ok(())
+}
+
+/// Runs the `fetch` subcommand. Returns `false` if any of the inputs could not
+/// be downloaded.
+pub fn fetch(matches: &ArgMatches) -> eyre::Result<bool> {
+    let selection = Selection::from_matches(matches)?;
+    let manager = InputManager::new(fetch_config(matches));
+    let force = matches.is_present("force");
+
+    let mut success = true;
+
+    for (number, _, _) in selection.days.iter() {
+        let cached = manager.cached_path(selection.year, *number);
+
+        let result = if force || !cached.exists() {
+            manager
+                .fetch(selection.year, *number)
+                .map(|p| (p, "fetched"))
+        } else {
+            Ok((cached, "cached"))
+        };
+
+        match result {
+            Ok((path, how)) => println!("Day {} {} ({})", number, path.display(), how),
+            Err(e) => {
+                println!("Day {} could not be fetched", number);
+                eprintln!("{:?}", e);
+                success = false;
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Runs the `submit` subcommand. Returns `false` unless the answer is correct.
+pub fn submit(matches: &ArgMatches) -> eyre::Result<bool> {
+    let year = year(matches)?;
+    let number = matches.value_of("day").unwrap().parse()?;
+    let part = matches.value_of("part").unwrap().parse()?;
+    let config = fetch_config(matches);
+
+    let answer = match matches.value_of("answer") {
+        Some(a) => Answer::parse(a),
+        None => {
+            let day = get_day(year, number)?;
+            let input = InputManager::new(config.clone()).resolve(year, number)?;
+
+            let (output_tx, _output_rx) = mpsc::channel();
+            let (debug_tx, _debug_rx) = mpsc::channel();
+            let ctx = Context::new(output_tx, debug_tx);
+
+            day.run_timed(input.display().to_string(), &ctx, part, MAIN_VARIANT)?
+                .answer
+        }
+    };
+
+    let receipt = Submitter::new(config).submit(year, number, part, &answer)?;
+    println!(
+        "{} day {} part {}: {}",
+        year,
+        number,
+        part,
+        answer.summary()
+    );
+    println!("  {}", receipt);
+
+    Ok(receipt.outcome == SubmitOutcome::Correct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::{config, temp_dir};
+
+    /// Benches part 1 of the example of day 1 against a baseline where it
+    /// took `solve`.
+    fn bench_against(solve: Duration, threshold: &str) -> bool {
+        let dir = temp_dir("bench");
+        let fetch = config(&dir, None);
+        let input = format!(
+            "{}/../inputs/2021/day1/small.txt",
+            env!("CARGO_MANIFEST_DIR")
+        );
+
+        let mut baseline = Baseline::default();
+        let key = (2021, 1, 1, MAIN_VARIANT.to_string(), input.clone());
+        baseline.insert(key, solve);
+        baseline.save(&fetch, "old").unwrap();
+
+        let matches = app().get_matches_from(vec![
+            "aoc",
+            "--year",
+            "2021",
+            "bench",
+            "--day",
+            "1",
+            "--part",
+            "1",
+            "--input",
+            &input,
+            "--warmup",
+            "0",
+            "--runs",
+            "1",
+            "--compare",
+            "old",
+            "--threshold",
+            threshold,
+        ]);
+        let (_, bench) = matches.subcommand();
+        bench_with(bench.unwrap(), fetch).unwrap()
+    }
+
+    #[test]
+    fn restarted_tuis_resume_from_the_environment() {
+        let resume = Resume {
+            year: 2021,
+            day: Some(2),
+            part: None,
+            input: None,
+        };
+
+        std::env::set_var(RESUME_ENV, resume.to_string());
+        let matches = app().get_matches_from(vec!["aoc"]);
+        std::env::remove_var(RESUME_ENV);
+
+        assert_eq!(options(&matches).unwrap().resume, Some(resume));
+    }
+
+    #[test]
+    fn regressions_fail_the_bench() {
+        assert!(!bench_against(Duration::from_nanos(1), "10"));
+    }
+
+    #[test]
+    fn benches_within_the_threshold_pass() {
+        assert!(bench_against(Duration::from_secs(3600), "10"));
+        assert!(bench_against(Duration::from_nanos(1), "1e15"));
+    }
+}
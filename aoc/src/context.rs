@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+use color_eyre::eyre;
+
+use crate::AocError;
+
+/// A flag shared between a running part and whoever may want to stop it.
+/// Cancellation is cooperative: parts are expected to check it in their
+/// long-running loops.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far along a running part is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    /// Between 0 and 1.
+    pub fraction: f64,
+    pub label: String,
+}
+
+/// Everything a part may talk to while it runs.
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub output: Sender<String>,
+    pub debug: Sender<String>,
+    pub progress: Option<Sender<Progress>>,
+    pub cancel: CancelToken,
+}
+
+impl Context {
+    pub fn new(output: Sender<String>, debug: Sender<String>) -> Self {
+        Self {
+            output,
+            debug,
+            progress: None,
+            cancel: CancelToken::new(),
+        }
+    }
+
+    pub fn with_progress(mut self, progress: Sender<Progress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Reports that `done` steps out of `total` are over.
+    pub fn progress(&self, done: usize, total: usize) {
+        let fraction = if total == 0 {
+            1.0
+        } else {
+            done as f64 / total as f64
+        };
+
+        self.progress_fraction(fraction, format!("step {}/{}", done, total));
+    }
+
+    /// Reports progress as a fraction, with a label of the part's choosing.
+    /// Nobody may be listening, in which case this does nothing.
+    pub fn progress_fraction<L>(&self, fraction: f64, label: L)
+    where
+        L: Into<String>,
+    {
+        if let Some(progress) = &self.progress {
+            let _ = progress.send(Progress {
+                fraction: fraction.clamp(0.0, 1.0),
+                label: label.into(),
+            });
+        }
+    }
+
+    /// Returns an error if the run was cancelled, to be used with `?`.
+    pub fn check_cancelled(&self) -> eyre::Result<()> {
+        if self.is_cancelled() {
+            Err(eyre::Report::new(AocError::Cancelled))
+        } else {
+            Ok(())
+        }
+    }
+}
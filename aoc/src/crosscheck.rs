@@ -0,0 +1,104 @@
+use std::sync::mpsc;
+
+use color_eyre::eyre;
+
+use crate::{Answer, Context, DynDay};
+
+/// What every variant of a part answered on one input.
+#[derive(Debug)]
+pub struct CrossCheck {
+    pub input: String,
+    pub part: usize,
+    /// The answer of each variant, or its error, rendered.
+    pub results: Vec<(&'static str, Result<Answer, String>)>,
+}
+
+impl CrossCheck {
+    /// Whether every variant gave the same answer, as [`Answer::matches`]
+    /// compares them. A variant failing counts as a disagreement.
+    pub fn agrees(&self) -> bool {
+        let mut answers = self.results.iter().map(|(_, r)| r.as_ref().ok());
+
+        match answers.next() {
+            Some(Some(first)) => answers.all(|a| matches!(a, Some(a) if a.matches(first))),
+            _ => false,
+        }
+    }
+
+    /// The answer every variant agrees on, if they do.
+    pub fn answer(&self) -> Option<&Answer> {
+        if self.agrees() {
+            self.results.first().and_then(|(_, r)| r.as_ref().ok())
+        } else {
+            None
+        }
+    }
+}
+
+/// Runs every variant of a part on an input, which is parsed only once. The
+/// error covers loading and parsing the input.
+pub fn cross_check(day: &dyn DynDay, input: &str, part: usize) -> eyre::Result<CrossCheck> {
+    let variants = day.variants(part);
+    let runs = variants.iter().map(|v| (part, *v)).collect::<Vec<_>>();
+
+    let (output, _output_rx) = mpsc::channel();
+    let (debug, _debug_rx) = mpsc::channel();
+    let ctx = Context::new(output, debug);
+
+    let results = day
+        .run_parts(input, &runs, &ctx)?
+        .into_iter()
+        .zip(variants)
+        .map(|(result, variant)| {
+            (
+                variant,
+                result.map(|s| s.answer).map_err(|e| format!("{:#}", e)),
+            )
+        })
+        .collect();
+
+    Ok(CrossCheck {
+        input: input.to_string(),
+        part,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(results: Vec<(&'static str, Result<Answer, String>)>) -> CrossCheck {
+        CrossCheck {
+            input: String::from("inputs/2021/day1/small.txt"),
+            part: 1,
+            results,
+        }
+    }
+
+    #[test]
+    fn agrees_on_matching_answers() {
+        let check = check(vec![
+            ("main", Ok(Answer::Integer(7))),
+            ("text", Ok(Answer::Text(String::from("7")))),
+        ]);
+        assert!(check.agrees());
+        assert_eq!(check.answer(), Some(&Answer::Integer(7)));
+    }
+
+    #[test]
+    fn disagrees_on_other_answers_and_errors() {
+        let other = check(vec![
+            ("main", Ok(Answer::Integer(7))),
+            ("slow", Ok(Answer::Integer(8))),
+        ]);
+        assert!(!other.agrees());
+        assert_eq!(other.answer(), None);
+
+        let failed = check(vec![
+            ("main", Ok(Answer::Integer(7))),
+            ("slow", Err(String::from("timed out"))),
+        ]);
+        assert!(!failed.agrees());
+    }
+}
@@ -0,0 +1,449 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use color_eyre::eyre::{self, WrapErr};
+use color_eyre::Help;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_CACHE_DIR: &str = "inputs";
+pub const DEFAULT_DATA_DIR: &str = ".aoc";
+
+/// Name of the personal puzzle input in the directory of each day.
+pub const REAL_INPUT: &str = "real.txt";
+
+/// Whatever can download a page on behalf of a logged in user.
+pub trait HttpClient: Send + Sync {
+    /// Fetches `url` with the given session cookie, and returns the body of
+    /// the response if it is successful.
+    fn get(&self, url: &str, session: &str) -> eyre::Result<String>;
+
+    /// Posts a form to `url` with the given session cookie, and returns the
+    /// body of the response if it is successful.
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> eyre::Result<String>;
+}
+
+/// The default client, blocking and with TLS support.
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!("aoc/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        }
+    }
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UreqClient {
+    fn body(url: &str, response: Result<ureq::Response, ureq::Error>) -> eyre::Result<String> {
+        match response {
+            Ok(r) => Ok(r.into_string()?),
+            Err(ureq::Error::Status(code, r)) => {
+                let body = r.into_string().unwrap_or_default();
+                Err(eyre::eyre!("{} answered {}: {}", url, code, body.trim()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> eyre::Result<String> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", session))
+            .call();
+
+        Self::body(url, response)
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> eyre::Result<String> {
+        let response = self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={}", session))
+            .send_form(form);
+
+        Self::body(url, response)
+    }
+}
+
+/// Where inputs are fetched from and kept, and where answers are posted.
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub base_url: String,
+    /// Value of the `session` cookie of a logged in user. Nothing is fetched
+    /// or submitted without it.
+    pub session: Option<String>,
+    pub cache_dir: PathBuf,
+    /// Where local records, such as past submissions, are kept.
+    pub data_dir: PathBuf,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            cache_dir: PathBuf::from(DEFAULT_CACHE_DIR),
+            data_dir: PathBuf::from(DEFAULT_DATA_DIR),
+        }
+    }
+}
+
+/// What can be done about an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputStatus {
+    Local,
+    /// A real input that isn't cached yet, but can be downloaded.
+    Fetchable,
+    Missing,
+}
+
+/// Cache directory the inputs of the days are listed in.
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn day_dir(cache_dir: &Path, year: u16, day: usize) -> PathBuf {
+    cache_dir.join(year.to_string()).join(format!("day{}", day))
+}
+
+/// Resolves the real input of a day, from the cache directory if it is there
+/// and from the website otherwise.
+pub struct InputManager {
+    config: FetchConfig,
+    client: Box<dyn HttpClient>,
+}
+
+impl InputManager {
+    pub fn new(config: FetchConfig) -> Self {
+        Self::with_client(config, UreqClient::new())
+    }
+
+    pub fn with_client<C>(config: FetchConfig, client: C) -> Self
+    where
+        C: HttpClient + 'static,
+    {
+        Self {
+            config,
+            client: Box::new(client),
+        }
+    }
+
+    pub fn config(&self) -> &FetchConfig {
+        &self.config
+    }
+
+    /// Lists the inputs of the days in `cache_dir`, so that their real
+    /// inputs are the ones a manager caching there fetches. The lists are
+    /// built once, when first needed, so this has to come first; later calls
+    /// change nothing.
+    pub fn set_cache_dir(cache_dir: &Path) {
+        let _ = CACHE_DIR.set(cache_dir.to_path_buf());
+    }
+
+    /// The cache directory the inputs of the days are listed in.
+    pub fn cache_dir() -> &'static Path {
+        CACHE_DIR.get_or_init(|| PathBuf::from(DEFAULT_CACHE_DIR))
+    }
+
+    /// Where an input of a day is listed, named as in `#[aoc(inputs(...))]`.
+    pub fn input_path(year: u16, day: usize, name: &str) -> PathBuf {
+        day_dir(Self::cache_dir(), year, day).join(format!("{}.txt", name))
+    }
+
+    /// The directory of the inputs of a day.
+    pub fn day_dir(&self, year: u16, day: usize) -> PathBuf {
+        day_dir(&self.config.cache_dir, year, day)
+    }
+
+    /// Where the real input of a day is cached.
+    pub fn cached_path(&self, year: u16, day: usize) -> PathBuf {
+        self.day_dir(year, day).join(REAL_INPUT)
+    }
+
+    pub fn url(&self, year: u16, day: usize) -> String {
+        format!(
+            "{}/{}/day/{}/input",
+            self.config.base_url.trim_end_matches('/'),
+            year,
+            day
+        )
+    }
+
+    /// The year and day whose real input is cached at `input`, if any.
+    fn day_of(&self, input: &str) -> Option<(u16, usize)> {
+        crate::YEARS
+            .iter()
+            .flat_map(|y| (1..=25).map(move |d| (*y, d)))
+            .find(|(y, d)| self.cached_path(*y, *d) == Path::new(input))
+    }
+
+    pub fn status(&self, input: &str) -> InputStatus {
+        if Path::new(input).exists() {
+            InputStatus::Local
+        } else if self.config.session.is_some() && self.day_of(input).is_some() {
+            InputStatus::Fetchable
+        } else {
+            InputStatus::Missing
+        }
+    }
+
+    /// The path of the real input of a day, downloaded first if needed.
+    pub fn resolve(&self, year: u16, day: usize) -> eyre::Result<PathBuf> {
+        let path = self.cached_path(year, day);
+
+        if !path.exists() {
+            self.fetch(year, day)?;
+        }
+
+        Ok(path)
+    }
+
+    /// Makes sure an input file exists, by fetching it if it is a real input.
+    /// Other missing inputs are left for the loader to report.
+    pub fn ensure(&self, input: &str) -> eyre::Result<()> {
+        match self.day_of(input) {
+            Some((year, day)) if !Path::new(input).exists() => self.resolve(year, day).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Downloads the real input of a day into the cache, replacing any copy
+    /// already there.
+    pub fn fetch(&self, year: u16, day: usize) -> eyre::Result<PathBuf> {
+        let session = self.config.session.as_deref().ok_or_else(|| {
+            eyre::eyre!("No session token to fetch the input of day {}", day)
+                .suggestion("Set AOC_SESSION or pass --session")
+        })?;
+
+        let url = self.url(year, day);
+        let body = self
+            .client
+            .get(&url, session)
+            .wrap_err_with(|| format!("Could not fetch the input of day {}", day))?;
+
+        let path = self.cached_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+        }
+
+        // Written aside then moved, so that an interrupted download doesn't
+        // leave a truncated input in the cache.
+        let partial = path.with_extension("part");
+        fs::write(&partial, body)
+            .wrap_err_with(|| format!("Could not write {}", partial.display()))?;
+        fs::rename(&partial, &path)?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex, Once};
+
+    /// A client that answers every request with the same page, and keeps a
+    /// line for each request it got.
+    #[derive(Clone)]
+    pub(crate) struct FakeClient {
+        page: String,
+        pub(crate) requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FakeClient {
+        pub(crate) fn new(page: &str) -> Self {
+            Self {
+                page: page.to_string(),
+                requests: Arc::default(),
+            }
+        }
+
+        pub(crate) fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl HttpClient for FakeClient {
+        fn get(&self, url: &str, session: &str) -> eyre::Result<String> {
+            let request = format!("GET {} session={}", url, session);
+            self.requests.lock().unwrap().push(request);
+            Ok(self.page.clone())
+        }
+
+        fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> eyre::Result<String> {
+            let form = form
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join("&");
+            let request = format!("POST {} session={} {}", url, session, form);
+            self.requests.lock().unwrap().push(request);
+            Ok(self.page.clone())
+        }
+    }
+
+    /// Installs the handler of color-eyre, which keeps the suggestions of
+    /// errors. Installing it twice panics, so tests share this.
+    pub(crate) fn install_handler() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| color_eyre::install().unwrap());
+    }
+
+    /// A directory of a test, removed with everything in it once dropped.
+    pub(crate) struct TempDir(PathBuf);
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempDir {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// An empty directory of its own for a test.
+    pub(crate) fn temp_dir(name: &str) -> TempDir {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-test-{}-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    pub(crate) fn config(dir: &Path, session: Option<&str>) -> FetchConfig {
+        FetchConfig {
+            base_url: "http://localhost:8080/".to_string(),
+            session: session.map(String::from),
+            cache_dir: dir.join("inputs"),
+            data_dir: dir.join(".aoc"),
+        }
+    }
+
+    fn real(manager: &InputManager) -> String {
+        manager.cached_path(2021, 1).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn days_list_their_inputs_in_the_cache() {
+        let manager = InputManager::new(FetchConfig::default());
+        let day = crate::get_day(2021, 1).unwrap();
+
+        assert_eq!(
+            InputManager::input_path(2021, 3, "small"),
+            Path::new("inputs/2021/day3/small.txt")
+        );
+        assert!(day.inputs().contains(&real(&manager).as_str()));
+    }
+
+    #[test]
+    fn fetch_writes_the_input_in_the_cache() {
+        let dir = temp_dir("fetch");
+        let client = FakeClient::new("199\n200\n");
+        let manager = InputManager::with_client(config(&dir, Some("abc")), client.clone());
+
+        let path = manager.fetch(2021, 1).unwrap();
+
+        assert_eq!(
+            client.requests(),
+            ["GET http://localhost:8080/2021/day/1/input session=abc"]
+        );
+        assert_eq!(path, dir.join("inputs/2021/day1/real.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "199\n200\n");
+        assert!(!path.with_extension("part").exists());
+    }
+
+    #[test]
+    fn fetch_writes_through_a_partial_file() {
+        let dir = temp_dir("partial");
+        let manager = InputManager::with_client(config(&dir, Some("abc")), FakeClient::new("1\n"));
+        let path = manager.cached_path(2021, 1);
+        // The partial file can't be written where a directory is.
+        fs::create_dir_all(path.with_extension("part")).unwrap();
+
+        assert!(manager.fetch(2021, 1).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn ensure_downloads_only_the_real_input() {
+        let dir = temp_dir("ensure");
+        let client = FakeClient::new("1\n");
+        let manager = InputManager::with_client(config(&dir, Some("abc")), client.clone());
+        let small = dir.join("inputs/2021/day1/small.txt");
+
+        manager.ensure(&small.to_string_lossy()).unwrap();
+        assert!(client.requests().is_empty());
+        assert!(!small.exists());
+
+        manager.ensure(&real(&manager)).unwrap();
+        manager.ensure(&real(&manager)).unwrap();
+        assert_eq!(client.requests().len(), 1);
+        assert!(manager.cached_path(2021, 1).exists());
+    }
+
+    #[test]
+    fn only_real_inputs_with_a_session_are_fetchable() {
+        let dir = temp_dir("status");
+        let with = InputManager::with_client(config(&dir, Some("abc")), FakeClient::new(""));
+        let without = InputManager::with_client(config(&dir, None), FakeClient::new(""));
+        let small = dir.join("inputs/2021/day1/small.txt");
+        let small = small.to_string_lossy();
+
+        assert_eq!(with.status(&real(&with)), InputStatus::Fetchable);
+        assert_eq!(without.status(&real(&without)), InputStatus::Missing);
+        assert_eq!(with.status(&small), InputStatus::Missing);
+
+        fs::create_dir_all(dir.join("inputs/2021/day1")).unwrap();
+        fs::write(dir.join("inputs/2021/day1/small.txt"), "1\n").unwrap();
+        assert_eq!(without.status(&small), InputStatus::Local);
+    }
+
+    #[test]
+    fn fetch_needs_a_session() {
+        install_handler();
+
+        let dir = temp_dir("session");
+        let client = FakeClient::new("");
+        let manager = InputManager::with_client(config(&dir, None), client.clone());
+
+        let error = format!("{:?}", manager.fetch(2021, 1).unwrap_err());
+
+        assert!(error.contains("No session token to fetch the input of day 1"));
+        assert!(error.contains("Set AOC_SESSION or pass --session"));
+        assert!(client.requests().is_empty());
+    }
+}
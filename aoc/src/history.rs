@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::fetch::FetchConfig;
+use crate::{Answer, AocError, Solution, Verdict};
+
+/// Name of the run log in the data directory.
+pub const HISTORY: &str = "history.jsonl";
+
+/// How a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunOutcome {
+    Finished,
+    /// Finished, but with an answer other than the expected one.
+    Wrong,
+    Error,
+    Cancelled,
+    TimedOut,
+}
+
+/// A completed run of a part, as kept in the run log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    pub year: u16,
+    pub day: usize,
+    pub part: usize,
+    pub variant: String,
+    pub input: String,
+    /// Commit the solutions were built from, marked `-dirty` if they had
+    /// uncommitted changes.
+    pub commit: Option<String>,
+    pub at: SystemTime,
+    pub outcome: RunOutcome,
+    pub answer: Option<Answer>,
+    pub duration: Option<Duration>,
+    pub error: Option<String>,
+}
+
+impl Run {
+    pub fn new(
+        year: u16,
+        day: usize,
+        part: usize,
+        variant: &str,
+        input: &str,
+        result: &eyre::Result<Solution>,
+    ) -> Self {
+        let (outcome, answer, duration, error) = match result {
+            Ok(s) => {
+                let outcome = match s.verdict {
+                    Verdict::Fail(_) => RunOutcome::Wrong,
+                    _ => RunOutcome::Finished,
+                };
+                (outcome, Some(s.answer.clone()), Some(s.duration), None)
+            }
+            Err(e) => match e.downcast_ref::<AocError>() {
+                Some(AocError::Cancelled) => (RunOutcome::Cancelled, None, None, None),
+                Some(AocError::TimedOut) => (RunOutcome::TimedOut, None, None, None),
+                _ => (RunOutcome::Error, None, None, Some(format!("{:#}", e))),
+            },
+        };
+
+        Self {
+            year,
+            day,
+            part,
+            variant: variant.to_string(),
+            input: input.to_string(),
+            commit: None,
+            at: SystemTime::now(),
+            outcome,
+            answer,
+            duration,
+            error,
+        }
+    }
+
+    /// What identifies the instance the run was made for.
+    pub fn key(&self) -> RunKey {
+        (
+            self.year,
+            self.day,
+            self.part,
+            self.variant.clone(),
+            self.input.clone(),
+        )
+    }
+}
+
+/// Year, day, part, variant and input of a run.
+pub type RunKey = (u16, usize, usize, String, String);
+
+/// The log of every completed run, one JSON object per line.
+pub struct History {
+    path: PathBuf,
+    commit: Option<String>,
+}
+
+impl History {
+    pub fn new(config: &FetchConfig) -> Self {
+        Self {
+            path: config.data_dir.join(HISTORY),
+            commit: current_commit(),
+        }
+    }
+
+    /// Appends a run to the log, stamped with the current commit.
+    pub fn record(&self, mut run: Run) -> eyre::Result<()> {
+        run.commit = self.commit.clone();
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .wrap_err_with(|| format!("Could not open {}", self.path.display()))?;
+
+        writeln!(file, "{}", serde_json::to_string(&run)?)?;
+        Ok(())
+    }
+
+    /// Every run recorded so far, oldest first. Lines that can't be read are
+    /// skipped.
+    pub fn runs(&self) -> eyre::Result<Vec<Run>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let log = fs::read_to_string(&self.path)
+            .wrap_err_with(|| format!("Could not read {}", self.path.display()))?;
+
+        Ok(log
+            .lines()
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect())
+    }
+
+    /// The last run of each instance.
+    pub fn latest(&self) -> eyre::Result<HashMap<RunKey, Run>> {
+        Ok(self.runs()?.into_iter().map(|r| (r.key(), r)).collect())
+    }
+}
+
+/// The commit checked out in the working directory, if it is a git
+/// repository. It is only looked up once.
+pub(crate) fn current_commit() -> Option<String> {
+    static COMMIT: OnceLock<Option<String>> = OnceLock::new();
+
+    COMMIT
+        .get_or_init(|| {
+            let output = Command::new("git")
+                .args(["describe", "--always", "--dirty"])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())?;
+
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .filter(|c| !c.is_empty())
+        })
+        .clone()
+}
@@ -0,0 +1,799 @@
+//! Solutions reloaded while the TUI runs, from the `aoc` library built as a
+//! `cdylib`.
+//!
+//! The binary and each loaded library have their own copy of this crate, of
+//! the standard library and of eyre, whose types can't be shared: allocators,
+//! vtables and type ids differ from one copy to the other. Only C types cross
+//! between them. Requests and responses are written as JSON, output and
+//! progress go through callbacks, and panics are caught in the library before
+//! they reach the boundary.
+//!
+//! Days of a library keep it loaded, so that it is unloaded once the days of
+//! the next one replaced all of them.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::error::Error;
+use std::ffi::{c_void, OsString};
+use std::fmt;
+use std::fs;
+use std::mem::ManuallyDrop;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use color_eyre::eyre::{self, WrapErr};
+use color_eyre::Help;
+use libloading::Library;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::bench::{BenchConfig, BenchReport};
+use crate::fetch::InputManager;
+use crate::{Answer, AocError, CancelToken, Context, DynDay, ParseError, Solution};
+
+type BoxedDay = Box<dyn DynDay + Send + Sync>;
+
+/// Version of the requests and responses, checked on load so that a library
+/// built from other sources is turned down rather than misread.
+const VERSION: u32 = 2;
+
+/// Symbols every build of the library exports.
+const VERSION_SYMBOL: &[u8] = b"aoc_hot_version";
+const SETUP: &[u8] = b"aoc_hot_setup";
+const CALL: &[u8] = b"aoc_hot_call";
+const FREE: &[u8] = b"aoc_hot_free";
+
+type VersionFn = unsafe extern "C" fn() -> u32;
+type SetupFn = unsafe extern "C" fn();
+type CallFn = unsafe extern "C" fn(*const u8, usize, *const Host) -> Buffer;
+type FreeFn = unsafe extern "C" fn(Buffer);
+
+/// The library loaded last, if any.
+static CURRENT: RwLock<Option<Arc<Loaded>>> = RwLock::new(None);
+/// Number of libraries loaded so far, to name their copies.
+static LOADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// A day, from the library loaded last, or from the binary until one is.
+pub fn get_day(year: u16, day: usize) -> eyre::Result<BoxedDay> {
+    let current = CURRENT.read().unwrap().clone();
+    match current {
+        Some(library) => HotDay::new(library, year, day).map(|d| Box::new(d) as BoxedDay),
+        None => crate::get_day(year, day),
+    }
+}
+
+/// Whether a library was loaded, and days come from it.
+pub fn is_loaded() -> bool {
+    CURRENT.read().unwrap().is_some()
+}
+
+/// A request of the binary to a library.
+#[derive(Debug, Serialize, Deserialize)]
+enum Request {
+    Info {
+        year: u16,
+        day: usize,
+        /// Where the binary lists the inputs of the days.
+        cache_dir: PathBuf,
+    },
+    Expected {
+        year: u16,
+        day: usize,
+        input: String,
+        part: usize,
+    },
+    Run {
+        year: u16,
+        day: usize,
+        input: String,
+        parts: Vec<(usize, String)>,
+    },
+    Bench {
+        year: u16,
+        day: usize,
+        input: String,
+        part: usize,
+        variant: String,
+        config: BenchConfig,
+    },
+}
+
+/// What the binary keeps of a day of a library.
+#[derive(Debug, Serialize, Deserialize)]
+struct Info {
+    title: String,
+    inputs: Vec<String>,
+    /// Variants of part 1 and part 2.
+    variants: Vec<Vec<String>>,
+}
+
+/// An error, as it crosses the boundary. The errors the binary looks for are
+/// kept as they are, any other one as its message.
+#[derive(Debug, Serialize, Deserialize)]
+struct Failure {
+    aoc: Option<AocError>,
+    parse: Option<ParseError>,
+    message: String,
+}
+
+impl Failure {
+    fn new(report: eyre::Report) -> Self {
+        let message = format!("{:#}", report);
+        let aoc = report.downcast_ref::<AocError>().copied();
+
+        Self {
+            aoc,
+            parse: report.downcast::<ParseError>().ok(),
+            message,
+        }
+    }
+
+    fn into_report(self) -> eyre::Report {
+        match (self.aoc, self.parse) {
+            (Some(e), _) => eyre::Report::new(e),
+            (_, Some(e)) => e.report(),
+            _ => eyre::eyre!(self.message),
+        }
+    }
+}
+
+type Response<T> = Result<T, Failure>;
+
+/// Bytes handed over by a library, to be given back to it to be freed.
+#[repr(C)]
+pub struct Buffer {
+    data: *mut u8,
+    len: usize,
+    capacity: usize,
+}
+
+impl Buffer {
+    fn new(bytes: Vec<u8>) -> Self {
+        let mut bytes = ManuallyDrop::new(bytes);
+        Self {
+            data: bytes.as_mut_ptr(),
+            len: bytes.len(),
+            capacity: bytes.capacity(),
+        }
+    }
+}
+
+/// Where a library sends what a running part prints.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum Stream {
+    Output = 0,
+    Debug = 1,
+}
+
+/// Callbacks into the binary, for a running part to talk to its context.
+#[repr(C)]
+pub struct Host {
+    context: *const c_void,
+    print: extern "C" fn(*const c_void, Stream, *const u8, usize),
+    progress: extern "C" fn(*const c_void, f64, *const u8, usize),
+    cancelled: extern "C" fn(*const c_void) -> bool,
+}
+
+impl Host {
+    fn new(ctx: &Context) -> Self {
+        Self {
+            context: ctx as *const Context as *const c_void,
+            print: host_print,
+            progress: host_progress,
+            cancelled: host_cancelled,
+        }
+    }
+}
+
+// The callbacks are only called by libraries with the context of the `Host`
+// they were given, and text they lend for the call.
+extern "C" fn host_print(context: *const c_void, stream: Stream, text: *const u8, len: usize) {
+    let ctx = unsafe { &*(context as *const Context) };
+    let text = String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(text, len) });
+
+    let _ = match stream {
+        Stream::Output => ctx.output.send(text.into_owned()),
+        Stream::Debug => ctx.debug.send(text.into_owned()),
+    };
+}
+
+extern "C" fn host_progress(context: *const c_void, fraction: f64, label: *const u8, len: usize) {
+    let ctx = unsafe { &*(context as *const Context) };
+    let label = String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(label, len) });
+
+    ctx.progress_fraction(fraction, label);
+}
+
+extern "C" fn host_cancelled(context: *const c_void) -> bool {
+    let ctx = unsafe { &*(context as *const Context) };
+    ctx.is_cancelled()
+}
+
+/// The callbacks of the binary, shared with the thread forwarding to them.
+struct HostRef<'a>(&'a Host);
+
+// The context behind the callbacks only holds senders and a cancellation
+// token, which may be used from any thread.
+unsafe impl Send for HostRef<'_> {}
+unsafe impl Sync for HostRef<'_> {}
+
+impl HostRef<'_> {
+    fn print(&self, stream: Stream, text: &str) {
+        (self.0.print)(self.0.context, stream, text.as_ptr(), text.len())
+    }
+
+    fn progress(&self, fraction: f64, label: &str) {
+        (self.0.progress)(self.0.context, fraction, label.as_ptr(), label.len())
+    }
+
+    fn cancelled(&self) -> bool {
+        (self.0.cancelled)(self.0.context)
+    }
+}
+
+/// Runs `f` with a context of the library, whose output, progress and
+/// cancellation are forwarded from and to the binary while it runs.
+fn forwarded<T>(host: &Host, cancel: CancelToken, f: impl FnOnce(&Context) -> T) -> T {
+    let host = HostRef(host);
+    let (output, output_rx) = mpsc::channel();
+    let (debug, debug_rx) = mpsc::channel();
+    let (progress, progress_rx) = mpsc::channel();
+    let ctx = Context::new(output, debug)
+        .with_progress(progress)
+        .with_cancel(cancel.clone());
+    let done = AtomicBool::new(false);
+    if host.cancelled() {
+        cancel.cancel();
+    }
+
+    thread::scope(|s| {
+        let (host, done) = (&host, &done);
+        s.spawn(move || loop {
+            // Read before draining, so that nothing sent before the end is
+            // left behind.
+            let last = done.load(Ordering::Acquire);
+
+            output_rx
+                .try_iter()
+                .for_each(|t| host.print(Stream::Output, &t));
+            debug_rx
+                .try_iter()
+                .for_each(|t| host.print(Stream::Debug, &t));
+            progress_rx
+                .try_iter()
+                .for_each(|p| host.progress(p.fraction, &p.label));
+            if host.cancelled() {
+                cancel.cancel();
+            }
+
+            if last {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        });
+
+        let result = f(&ctx);
+        done.store(true, Ordering::Release);
+        result
+    })
+}
+
+/// Runs `f`, turning its error or panic into a failure.
+fn guard<T>(f: impl FnOnce() -> eyre::Result<T>) -> Response<T> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.map_err(Failure::new),
+        Err(_) => {
+            let panic = LAST_PANIC.with(|p| p.borrow_mut().take());
+            Err(Failure::new(eyre::eyre!(
+                panic.unwrap_or_else(|| String::from("panicked"))
+            )))
+        }
+    }
+}
+
+fn respond(request: Request, host: &Host) -> eyre::Result<Vec<u8>> {
+    let bytes = match request {
+        Request::Info {
+            year,
+            day,
+            cache_dir,
+        } => serde_json::to_vec(&guard(|| {
+            InputManager::set_cache_dir(&cache_dir);
+            let day = crate::get_day(year, day)?;
+            Ok(Info {
+                title: day.title(),
+                inputs: day.inputs().iter().map(|i| i.to_string()).collect(),
+                variants: (1..=2)
+                    .map(|part| day.variants(part).iter().map(|v| v.to_string()).collect())
+                    .collect(),
+            })
+        })),
+        Request::Expected {
+            year,
+            day,
+            input,
+            part,
+        } => serde_json::to_vec(&guard(|| {
+            Ok(crate::get_day(year, day)?.expected(&input, part))
+        })),
+        Request::Run {
+            year,
+            day,
+            input,
+            parts,
+        } => serde_json::to_vec(&guard(|| {
+            let day = crate::get_day(year, day)?;
+            let parts = parts
+                .iter()
+                .map(|(part, variant)| (*part, variant.as_str()))
+                .collect::<Vec<_>>();
+
+            let results = forwarded(host, CancelToken::new(), |ctx| {
+                day.run_parts(&input, &parts, ctx)
+            })?;
+            Ok(results
+                .into_iter()
+                .map(|r| r.map_err(Failure::new))
+                .collect::<Vec<_>>())
+        })),
+        Request::Bench {
+            year,
+            day,
+            input,
+            part,
+            variant,
+            config,
+        } => serde_json::to_vec(&guard(|| {
+            let day = crate::get_day(year, day)?;
+            let cancel = CancelToken::new();
+            forwarded(host, cancel.clone(), |_| {
+                day.bench(&input, part, &variant, config, &cancel)
+            })
+        })),
+    };
+
+    Ok(bytes?)
+}
+
+/// The version of the requests the library answers.
+#[no_mangle]
+pub extern "C" fn aoc_hot_version() -> u32 {
+    VERSION
+}
+
+/// Installs the error hook of the library, and a panic hook that keeps panics
+/// for [`guard`] instead of printing them over the TUI.
+#[no_mangle]
+pub extern "C" fn aoc_hot_setup() {
+    let (_, eyre_hook) = color_eyre::config::HookBuilder::default().into_hooks();
+    // Already installed if the library was loaded before.
+    let _ = eyre_hook.install();
+
+    panic::set_hook(Box::new(|info| {
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            String::from("Box<dyn Any>")
+        };
+
+        let panic = match info.location() {
+            Some(l) => format!("panicked at {}: {}", l, message),
+            None => format!("panicked: {}", message),
+        };
+        LAST_PANIC.with(|p| *p.borrow_mut() = Some(panic));
+    }));
+}
+
+/// Answers a request, written as JSON, with a response written the same way,
+/// to be freed with [`aoc_hot_free`].
+///
+/// # Safety
+///
+/// `request` points to `len` bytes, and `host` to callbacks that stay valid
+/// until the call returns.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_hot_call(request: *const u8, len: usize, host: *const Host) -> Buffer {
+    let request = std::slice::from_raw_parts(request, len);
+    let host = &*host;
+
+    let response = panic::catch_unwind(AssertUnwindSafe(|| {
+        let request = serde_json::from_slice(request).wrap_err("Unreadable request")?;
+        respond(request, host)
+    }))
+    .unwrap_or_else(|_| Err(eyre::eyre!("panicked while answering a request")));
+
+    Buffer::new(response.unwrap_or_else(|e| {
+        serde_json::to_vec(&Response::<()>::Err(Failure::new(e))).unwrap_or_default()
+    }))
+}
+
+/// Frees a response of [`aoc_hot_call`].
+///
+/// # Safety
+///
+/// `buffer` was returned by `aoc_hot_call` of this library, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_hot_free(buffer: Buffer) {
+    drop(Vec::from_raw_parts(
+        buffer.data,
+        buffer.len,
+        buffer.capacity,
+    ));
+}
+
+/// A loaded library.
+struct Loaded {
+    call: CallFn,
+    free: FreeFn,
+    // Dropped last, once its functions can't be called anymore. Tests call
+    // the functions of the binary itself, which has none.
+    _library: Option<Library>,
+}
+
+impl Loaded {
+    fn call<T: DeserializeOwned>(&self, request: &Request, ctx: &Context) -> eyre::Result<T> {
+        let request = serde_json::to_vec(request)?;
+        let host = Host::new(ctx);
+
+        // The library only reads the request and calls back while it answers.
+        let response = unsafe {
+            let buffer = (self.call)(request.as_ptr(), request.len(), &host);
+            let bytes = std::slice::from_raw_parts(buffer.data, buffer.len).to_vec();
+            (self.free)(buffer);
+            bytes
+        };
+
+        serde_json::from_slice::<Response<T>>(&response)
+            .wrap_err("Unreadable response from the solutions")?
+            .map_err(Failure::into_report)
+    }
+}
+
+/// A context whose output nobody reads, for requests that print nothing.
+fn quiet() -> Context {
+    Context::new(mpsc::channel().0, mpsc::channel().0)
+}
+
+/// The same string for the same text, for names lent for the whole run. They
+/// are only a few, even over many reloads.
+fn intern(s: String) -> &'static str {
+    static INTERNED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let mut interned = INTERNED.lock().unwrap();
+    match interned.get(s.as_str()) {
+        Some(s) => s,
+        None => {
+            let s: &'static str = Box::leak(s.into_boxed_str());
+            interned.insert(s);
+            s
+        }
+    }
+}
+
+/// A day of a loaded library, which it keeps loaded.
+struct HotDay {
+    library: Arc<Loaded>,
+    year: u16,
+    day: usize,
+    title: String,
+    inputs: Vec<&'static str>,
+    variants: Vec<Vec<&'static str>>,
+}
+
+impl HotDay {
+    fn new(library: Arc<Loaded>, year: u16, day: usize) -> eyre::Result<Self> {
+        let request = Request::Info {
+            year,
+            day,
+            cache_dir: InputManager::cache_dir().to_path_buf(),
+        };
+        let info: Info = library.call(&request, &quiet())?;
+
+        Ok(Self {
+            library,
+            year,
+            day,
+            title: info.title,
+            inputs: info.inputs.into_iter().map(intern).collect(),
+            variants: info
+                .variants
+                .into_iter()
+                .map(|v| v.into_iter().map(intern).collect())
+                .collect(),
+        })
+    }
+}
+
+impl DynDay for HotDay {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn inputs(&self) -> &[&'static str] {
+        &self.inputs
+    }
+
+    fn expected(&self, input: &str, part: usize) -> Option<Answer> {
+        let request = Request::Expected {
+            year: self.year,
+            day: self.day,
+            input: input.to_string(),
+            part,
+        };
+        self.library.call(&request, &quiet()).unwrap_or(None)
+    }
+
+    fn variants(&self, part: usize) -> Vec<&'static str> {
+        part.checked_sub(1)
+            .and_then(|p| self.variants.get(p))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn run_parts(
+        &self,
+        input: &str,
+        parts: &[(usize, &str)],
+        ctx: &Context,
+    ) -> eyre::Result<Vec<eyre::Result<Solution>>> {
+        let request = Request::Run {
+            year: self.year,
+            day: self.day,
+            input: input.to_string(),
+            parts: parts.iter().map(|(p, v)| (*p, v.to_string())).collect(),
+        };
+
+        let results: Vec<Response<Solution>> = self.library.call(&request, ctx)?;
+        Ok(results
+            .into_iter()
+            .map(|r| r.map_err(Failure::into_report))
+            .collect())
+    }
+
+    fn bench(
+        &self,
+        input: &str,
+        part: usize,
+        variant: &str,
+        config: BenchConfig,
+        cancel: &CancelToken,
+    ) -> eyre::Result<BenchReport> {
+        let request = Request::Bench {
+            year: self.year,
+            day: self.day,
+            input: input.to_string(),
+            part,
+            variant: variant.to_string(),
+            config,
+        };
+
+        self.library
+            .call(&request, &quiet().with_cancel(cancel.clone()))
+    }
+}
+
+/// The output of a build of the library that failed, compiler errors included.
+#[derive(Debug)]
+pub struct BuildError(pub String);
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to build the solutions")
+    }
+}
+
+impl Error for BuildError {}
+
+/// Builds the library with cargo, in the target directory and profile of the
+/// running binary, and returns the path of the library.
+pub fn build() -> eyre::Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    let profile = exe
+        .parent()
+        .ok_or_else(|| eyre::eyre!("{} has no parent directory", exe.display()))?;
+    let target = profile
+        .parent()
+        .ok_or_else(|| eyre::eyre!("{} isn't in a target directory", exe.display()))?;
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command = Command::new(cargo);
+    command
+        .args([
+            "build",
+            "--lib",
+            "--quiet",
+            "--color",
+            "never",
+            "--manifest-path",
+        ])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(target);
+    if profile.ends_with("release") {
+        command.arg("--release");
+    }
+
+    let output = command
+        .output()
+        .wrap_err("Failed to run cargo")
+        .suggestion("Check that cargo is in the PATH, or set CARGO")?;
+
+    if !output.status.success() {
+        return Err(BuildError(String::from_utf8_lossy(&output.stderr).into_owned()).into());
+    }
+
+    Ok(profile.join(format!("{}aoc{}", DLL_PREFIX, DLL_SUFFIX)))
+}
+
+/// Loads a build of the library, after which [`get_day`] goes through it.
+///
+/// The library is copied first, as a path that was loaded already would give
+/// back the library loaded then. The copy is removed once loaded.
+pub fn load(path: &Path) -> eyre::Result<()> {
+    let copy = path.with_file_name(format!(
+        "{}aoc-hot-{}-{}{}",
+        DLL_PREFIX,
+        std::process::id(),
+        LOADS.fetch_add(1, Ordering::Relaxed),
+        DLL_SUFFIX
+    ));
+    fs::copy(path, &copy).wrap_err_with(|| format!("Failed to copy {}", path.display()))?;
+
+    // Only the functions above are looked up, with the C signatures they are
+    // exported with.
+    let loaded = unsafe {
+        Library::new(&copy).and_then(|library| {
+            let version = *library.get::<VersionFn>(VERSION_SYMBOL)?;
+            let setup = *library.get::<SetupFn>(SETUP)?;
+            let call = *library.get::<CallFn>(CALL)?;
+            let free = *library.get::<FreeFn>(FREE)?;
+            Ok((library, version, setup, call, free))
+        })
+    };
+    // Loaded libraries stay mapped once their file is gone, where they can be.
+    let _ = fs::remove_file(&copy);
+
+    let (library, version, setup, call, free) =
+        loaded.wrap_err_with(|| format!("Failed to load {}", path.display()))?;
+
+    let version = unsafe { version() };
+    if version != VERSION {
+        return Err(eyre::eyre!(
+            "{} answers requests of version {}, not {}",
+            path.display(),
+            version,
+            VERSION
+        ))
+        .suggestion("Restart the TUI, as its binary is older than the solutions");
+    }
+    unsafe { setup() };
+
+    *CURRENT.write().unwrap() = Some(Arc::new(Loaded {
+        call,
+        free,
+        _library: Some(library),
+    }));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::temp_dir;
+
+    /// The functions of the binary, called as those of a library would be.
+    fn binary() -> Arc<Loaded> {
+        Arc::new(Loaded {
+            call: aoc_hot_call,
+            free: aoc_hot_free,
+            _library: None,
+        })
+    }
+
+    fn example(day: usize) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(format!("../inputs/2021/day{}/small.txt", day))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn days_cross_with_their_info() {
+        let day = HotDay::new(binary(), 2021, 1).unwrap();
+
+        assert_eq!(day.title(), "Sonar Sweep");
+        assert_eq!(
+            day.inputs(),
+            ["inputs/2021/day1/small.txt", "inputs/2021/day1/real.txt"]
+        );
+        assert_eq!(day.variants(2), ["main", "offset"]);
+        assert!(day.variants(3).is_empty());
+        assert_eq!(day.expected(&example(1), 2), Some(Answer::Integer(5)));
+    }
+
+    #[test]
+    fn runs_cross_with_their_output() {
+        let day = HotDay::new(binary(), 2021, 3).unwrap();
+        let (output, output_rx) = mpsc::channel();
+        let (debug, _debug_rx) = mpsc::channel();
+        let ctx = Context::new(output, debug);
+
+        let results = day
+            .run_parts(&example(3), &[(1, "main"), (2, "main")], &ctx)
+            .unwrap();
+        let answers = results
+            .into_iter()
+            .map(|r| r.unwrap().answer)
+            .collect::<Vec<_>>();
+
+        assert_eq!(answers, [Answer::Integer(198), Answer::Integer(230)]);
+        assert_eq!(
+            output_rx.try_iter().collect::<String>(),
+            "oxygen: 23, co2: 10\n"
+        );
+    }
+
+    #[test]
+    fn errors_cross_as_they_are_looked_for() {
+        let dir = temp_dir("hot");
+        let input = dir.join("small.txt");
+        fs::write(&input, "00100\n1111\n").unwrap();
+        let input = input.to_string_lossy();
+
+        let day = HotDay::new(binary(), 2021, 3).unwrap();
+        let error = day
+            .run_timed(input.to_string(), &quiet(), 1, "main")
+            .unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.file.as_deref(), Some(&*input));
+
+        let error = HotDay::new(binary(), 2021, 25).err().unwrap();
+        assert_eq!(
+            error.downcast_ref::<AocError>(),
+            Some(&AocError::UnimplementedDay)
+        );
+    }
+
+    #[test]
+    fn cancellation_crosses() {
+        let day = HotDay::new(binary(), 2021, 1).unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+
+        let ctx = quiet().with_cancel(cancel);
+        let error = day.run_timed(example(1), &ctx, 1, "main").unwrap_err();
+        assert_eq!(error.downcast_ref::<AocError>(), Some(&AocError::Cancelled));
+    }
+
+    #[test]
+    fn libraries_that_cant_be_loaded() {
+        let dir = temp_dir("load");
+
+        let missing = dir.join(format!("{}aoc{}", DLL_PREFIX, DLL_SUFFIX));
+        let error = load(&missing).unwrap_err();
+        assert!(error.to_string().starts_with("Failed to copy"));
+
+        fs::write(&missing, "not a library").unwrap();
+        let error = load(&missing).unwrap_err();
+        assert!(error.to_string().starts_with("Failed to load"));
+        // The copy is gone, and nothing was loaded.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        assert!(!is_loaded());
+    }
+}
@@ -2,85 +2,383 @@ use std::error::Error;
 use std::fmt;
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader};
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{self, Sender};
 use std::time::{Duration, Instant};
 
 use color_eyre::eyre;
 use color_eyre::Help;
 use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
 
+pub use aoc_derive::aoc;
 use aoc_derive::days;
 
+mod answer;
+pub use answer::*;
+
+pub mod bench;
+use bench::{BenchConfig, BenchReport, Stats};
+
+pub mod baseline;
+pub mod cli;
+pub mod crosscheck;
+pub mod fetch;
+pub mod history;
+pub mod hot;
+pub mod report;
+pub mod submit;
 pub mod ui;
 
-days!(3);
+mod parse;
+pub use parse::ParseError;
+
+mod context;
+pub use context::*;
+
+days!(2021 => *);
 
-pub type Day = (usize, Option<Box<dyn AocDay + Send + Sync>>);
+pub type Day = (usize, Option<Box<dyn DynDay + Send + Sync>>);
 
-pub fn days() -> Vec<Day> {
-    (1..=25).map(|i| (i, get_day(i).ok())).collect()
+pub fn days(year: u16) -> Vec<Day> {
+    (1..=25).map(|i| (i, hot::get_day(year, i).ok())).collect()
 }
 
-pub trait AocDay {
-    fn title(&self) -> String;
-    fn inputs(&self) -> &[&'static str];
-    fn part_1(
-        &self,
-        input: Vec<String>,
-        output: Sender<String>,
-        debug: Sender<String>,
-    ) -> eyre::Result<()>;
+/// The most recent implemented year, used when none is given.
+pub fn latest_year() -> u16 {
+    *YEARS.last().unwrap()
+}
 
-    fn part_2(
-        &self,
-        input: Vec<String>,
-        output: Sender<String>,
-        debug: Sender<String>,
-    ) -> eyre::Result<()>;
+pub fn format_duration(d: &Duration) -> String {
+    format!(
+        "{:02}:{:02}.{:03}",
+        d.as_secs() / 60,
+        d.as_secs() % 60,
+        d.as_millis() % 1000
+    )
+}
+
+/// What `#[aoc]` declares about a day.
+pub trait DayInfo {
+    const YEAR: u16;
+    const DAY: usize;
+    const TITLE: &'static str;
+
+    /// Paths of the inputs of the day.
+    fn input_paths() -> &'static [&'static str];
+}
+
+/// Name of the variant made of `part_1` and `part_2`.
+pub const MAIN_VARIANT: &str = "main";
+
+pub type Solver<D> = fn(&D, &<D as AocDay>::Parsed, &Context) -> eyre::Result<Answer>;
+
+/// Another way of solving a part, to compare with `part_1` or `part_2`.
+pub struct Variant<D: AocDay> {
+    pub name: &'static str,
+    pub solve: Solver<D>,
+}
+
+impl<D: AocDay> Variant<D> {
+    pub fn new(name: &'static str, solve: Solver<D>) -> Self {
+        Self { name, solve }
+    }
+}
+
+pub trait AocDay: DayInfo {
+    /// The input once parsed, shared by both parts.
+    type Parsed;
+
+    fn title(&self) -> String {
+        Self::TITLE.to_owned()
+    }
+
+    fn inputs(&self) -> &[&'static str] {
+        Self::input_paths()
+    }
+
+    fn parse(&self, input: Vec<String>) -> eyre::Result<Self::Parsed>;
+    fn part_1(&self, input: &Self::Parsed, ctx: &Context) -> eyre::Result<Answer>;
+    fn part_2(&self, input: &Self::Parsed, ctx: &Context) -> eyre::Result<Answer>;
+
+    /// Other solvers of a part, on top of the main one.
+    fn variants(&self, _part: usize) -> Vec<Variant<Self>>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// The expected answer of a part for a given input, if known. Defaults to
+    /// reading the `.expected` sidecar file next to the input.
+    fn expected(&self, input: &str, part: usize) -> Option<Answer> {
+        expected_from_sidecar(input, part)
+    }
 
     fn println(&self, s: String, channel: &Sender<String>) {
         channel.send(format!("{}\n", s)).unwrap();
     }
 
+    fn solve(&self, input: &Self::Parsed, ctx: &Context, part: usize) -> eyre::Result<Answer> {
+        if part == 1 {
+            self.part_1(input, ctx)
+        } else if part == 2 {
+            self.part_2(input, ctx)
+        } else {
+            panic!("Invalid part")
+        }
+    }
+}
+
+/// The solver of a part going by the given name.
+fn solver<T: AocDay>(day: &T, part: usize, variant: &str) -> eyre::Result<Solver<T>> {
+    if variant == MAIN_VARIANT {
+        return Ok(match part {
+            1 => T::part_1,
+            2 => T::part_2,
+            _ => panic!("Invalid part"),
+        });
+    }
+
+    day.variants(part)
+        .into_iter()
+        .find(|v| v.name == variant)
+        .map(|v| v.solve)
+        .ok_or_else(|| eyre::eyre!("Part {} has no variant named {}", part, variant))
+        .with_suggestion(|| format!("Try one of: {}", DynDay::variants(day, part).join(", ")))
+}
+
+pub fn load_input(path: &str) -> eyre::Result<Vec<String>> {
+    Ok(
+        BufReader::new(OpenOptions::new().read(true).write(false).open(path)?)
+            .lines()
+            .map_while(Result::ok)
+            .collect(),
+    )
+}
+
+/// The object-safe face of [`AocDay`], with its `Parsed` type erased. Every
+/// `AocDay` implements it, and it is what gets boxed and shared with the runners.
+pub trait DynDay {
+    fn title(&self) -> String;
+    fn inputs(&self) -> &[&'static str];
+    fn expected(&self, input: &str, part: usize) -> Option<Answer>;
+
+    /// Names of the solvers of a part, the main one first.
+    fn variants(&self, part: usize) -> Vec<&'static str>;
+
+    /// Loads and parses an input once, then runs each of the given parts on
+    /// it, with the given variant. The outer error covers loading and parsing,
+    /// the inner ones each part.
+    fn run_parts(
+        &self,
+        input: &str,
+        parts: &[(usize, &str)],
+        ctx: &Context,
+    ) -> eyre::Result<Vec<eyre::Result<Solution>>>;
+
+    /// Runs a part repeatedly, discarding its text output, and collects
+    /// statistics on the time spent loading, parsing and solving. Stops
+    /// between runs if cancelled.
+    fn bench(
+        &self,
+        input: &str,
+        part: usize,
+        variant: &str,
+        config: BenchConfig,
+        cancel: &CancelToken,
+    ) -> eyre::Result<BenchReport>;
+
     fn run_timed(
         &self,
         input: String,
-        output: Sender<String>,
-        debug: Sender<String>,
+        ctx: &Context,
         part: usize,
-    ) -> eyre::Result<Duration> {
-        let input = BufReader::new(OpenOptions::new().read(true).write(false).open(input)?)
-            .lines()
-            .filter_map(|s| s.ok())
-            .collect();
+        variant: &str,
+    ) -> eyre::Result<Solution> {
+        self.run_parts(&input, &[(part, variant)], ctx)?
+            .pop()
+            .unwrap()
+    }
+}
+
+impl<T: AocDay> DynDay for T {
+    fn title(&self) -> String {
+        AocDay::title(self)
+    }
+
+    fn inputs(&self) -> &[&'static str] {
+        AocDay::inputs(self)
+    }
+
+    fn expected(&self, input: &str, part: usize) -> Option<Answer> {
+        AocDay::expected(self, input, part)
+    }
+
+    fn variants(&self, part: usize) -> Vec<&'static str> {
+        std::iter::once(MAIN_VARIANT)
+            .chain(AocDay::variants(self, part).iter().map(|v| v.name))
+            .collect()
+    }
+
+    fn run_parts(
+        &self,
+        input: &str,
+        parts: &[(usize, &str)],
+        ctx: &Context,
+    ) -> eyre::Result<Vec<eyre::Result<Solution>>> {
+        let lines = load_input(input)?;
 
         let start = Instant::now();
+        let parsed = self
+            .parse(lines)
+            .map_err(|e| ParseError::locate(e, input))?;
+        let parse = start.elapsed();
 
-        if part == 1 {
-            self.part_1(input, output, debug)?;
-        } else if part == 2 {
-            self.part_2(input, output, debug)?;
-        } else {
-            panic!("Invalid part")
+        Ok(parts
+            .iter()
+            .map(|&(part, variant)| {
+                let solve = solver(self, part, variant)?;
+                ctx.check_cancelled()?;
+
+                let start = Instant::now();
+                let answer = solve(self, &parsed, ctx)?;
+                let duration = start.elapsed();
+
+                Ok(Solution {
+                    verdict: Verdict::new(&answer, AocDay::expected(self, input, part)),
+                    answer,
+                    parse,
+                    duration,
+                })
+            })
+            .collect())
+    }
+
+    fn bench(
+        &self,
+        input: &str,
+        part: usize,
+        variant: &str,
+        config: BenchConfig,
+        cancel: &CancelToken,
+    ) -> eyre::Result<BenchReport> {
+        let run_part = solver(self, part, variant)?;
+        let (output, output_rx) = mpsc::channel();
+        let (debug, debug_rx) = mpsc::channel();
+        let ctx = Context::new(output, debug).with_cancel(cancel.clone());
+
+        let run = || -> eyre::Result<([Duration; 3], Answer)> {
+            ctx.check_cancelled()?;
+
+            let start = Instant::now();
+            let lines = load_input(input)?;
+            let load = start.elapsed();
+
+            let start = Instant::now();
+            let parsed = self
+                .parse(lines)
+                .map_err(|e| ParseError::locate(e, input))?;
+            let parse = start.elapsed();
+
+            let start = Instant::now();
+            let answer = run_part(self, &parsed, &ctx)?;
+            let solve = start.elapsed();
+
+            output_rx.try_iter().for_each(drop);
+            debug_rx.try_iter().for_each(drop);
+
+            Ok(([load, parse, solve], answer))
+        };
+
+        for _ in 0..config.warmup {
+            run()?;
+        }
+
+        let mut load = Vec::new();
+        let mut parse = Vec::new();
+        let mut solve = Vec::new();
+        let mut answer = None;
+        let start = Instant::now();
+
+        while !config.is_done(solve.len(), start.elapsed()) {
+            let ([l, p, s], a) = run()?;
+            load.push(l);
+            parse.push(p);
+            solve.push(s);
+            answer = Some(a);
         }
 
-        Ok(start.elapsed())
+        Ok(BenchReport {
+            answer: answer.unwrap(),
+            load: Stats::new(&load),
+            parse: Stats::new(&parse),
+            solve: Stats::new(&solve),
+        })
+    }
+}
+
+/// Runs every variant of a part on an input of a day, and panics unless each
+/// gives the expected answer. Inputs are found from the root of the workspace,
+/// where the binary runs, rather than from the directory of the tests. Inputs
+/// missing on disk, such as real ones on a fresh checkout, and inputs without
+/// an expected answer are skipped.
+#[cfg(test)]
+fn check_expected_answer(day: &dyn DynDay, input: usize, part: usize) {
+    let input = day.inputs()[input];
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(input);
+    let path = path.to_string_lossy();
+
+    if !std::path::Path::new(&*path).exists() {
+        eprintln!("skipping {}: input file is missing", input);
+        return;
+    }
+
+    if day.expected(&path, part).is_none() {
+        eprintln!("skipping {}: no expected answer for part {}", input, part);
+        return;
+    }
+
+    for variant in day.variants(part) {
+        let (output, _output_rx) = std::sync::mpsc::channel();
+        let (debug, _debug_rx) = std::sync::mpsc::channel();
+        let ctx = Context::new(output, debug);
+
+        match day.run_timed(path.to_string(), &ctx, part, variant) {
+            Ok(solution) => assert_eq!(
+                solution.verdict,
+                Verdict::Pass,
+                "{} part {} ({}): got {}",
+                input,
+                part,
+                variant,
+                solution.answer
+            ),
+            Err(e) => panic!("{} part {} ({}) failed: {:?}", input, part, variant, e),
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AocError {
+    UnimplementedYear,
     UnimplementedDay,
     NonExistentDay,
+    Cancelled,
+    TimedOut,
 }
 
 impl fmt::Display for AocError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            AocError::UnimplementedYear => write!(f, "Year not implemented yet"),
             AocError::UnimplementedDay => write!(f, "Day not implemented yet"),
             AocError::NonExistentDay => {
                 write!(f, "Non-existent day")
             }
+            AocError::Cancelled => write!(f, "Run cancelled"),
+            AocError::TimedOut => write!(f, "Run timed out"),
         }
     }
 }
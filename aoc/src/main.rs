@@ -2,9 +2,44 @@ use color_eyre::Report;
 use tracing_subscriber::EnvFilter;
 
 fn main() -> Result<(), Report> {
-    setup()?;
+    let matches = aoc::cli::app().get_matches();
 
-    aoc::ui::run()?;
+    setup()?;
+    aoc::fetch::InputManager::set_cache_dir(&aoc::cli::fetch_config(&matches).cache_dir);
+
+    match matches.subcommand() {
+        ("run", Some(m)) => {
+            if !aoc::cli::run(m)? {
+                std::process::exit(1);
+            }
+        }
+        ("bench", Some(m)) => {
+            if !aoc::cli::bench(m)? {
+                std::process::exit(1);
+            }
+        }
+        ("check", Some(m)) => {
+            if !aoc::cli::check(m)? {
+                std::process::exit(1);
+            }
+        }
+        ("report", Some(m)) => aoc::cli::report(m)?,
+        ("submit", Some(m)) => {
+            if !aoc::cli::submit(m)? {
+                std::process::exit(1);
+            }
+        }
+        ("fetch", Some(m)) => {
+            if !aoc::cli::fetch(m)? {
+                std::process::exit(1);
+            }
+        }
+        _ => {
+            if let aoc::ui::Exit::Restart(resume) = aoc::ui::run(aoc::cli::options(&matches)?)? {
+                aoc::cli::restart(&resume)?
+            }
+        }
+    }
 
     Ok(())
 }
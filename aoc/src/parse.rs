@@ -0,0 +1,154 @@
+use std::error::Error;
+use std::fmt;
+
+use color_eyre::eyre;
+use color_eyre::{Section, SectionExt};
+use serde::{Deserialize, Serialize};
+
+/// An error in an input file, located down to the offending character.
+///
+/// Days build it with the line and column they are at; the file is filled in
+/// by the runner, which is the only one to know it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParseError {
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, in characters.
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Builds an error from 0-based line and column indices, as given by
+    /// `enumerate()` and `char_indices()`.
+    pub fn new<T, R>(line: usize, column: usize, text: T, reason: R) -> Self
+    where
+        T: Into<String>,
+        R: Into<String>,
+    {
+        Self {
+            file: None,
+            line: line + 1,
+            column: column + 1,
+            text: text.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Builds an error pointing at `token`, which must be a slice of `text`.
+    pub fn at_token<R>(line: usize, text: &str, token: &str, reason: R) -> Self
+    where
+        R: Into<String>,
+    {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|o| *o <= text.len())
+            .unwrap_or(0);
+
+        Self::new(line, text[..offset].chars().count(), text, reason)
+    }
+
+    pub fn in_file<F>(mut self, file: F) -> Self
+    where
+        F: Into<String>,
+    {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// The offending line, with a caret under the offending character.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let padding = " ".repeat(number.len());
+
+        format!(
+            "{} | {}\n{} | {}^",
+            number,
+            self.text,
+            padding,
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+
+    /// Turns the error into a report carrying the snippet as a section.
+    pub fn report(self) -> eyre::Report {
+        let snippet = self.snippet();
+        eyre::Report::new(self).section(snippet.header("Input:"))
+    }
+
+    /// Attaches the file to a report if it wraps a `ParseError`, and leaves
+    /// any other report untouched.
+    pub fn locate(report: eyre::Report, file: &str) -> eyre::Report {
+        match report.downcast::<ParseError>() {
+            Ok(e) => e.in_file(file).report(),
+            Err(report) => report,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            self.reason
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_token() {
+        let text = "forward x5";
+        let e = ParseError::at_token(3, text, &text[8..], "invalid amount");
+        assert_eq!((e.line, e.column), (4, 9));
+
+        // Columns count characters, not bytes.
+        let text = "é → 12";
+        let e = ParseError::at_token(0, text, &text[7..], "");
+        assert_eq!(e.column, 5);
+
+        // A token from elsewhere points at the start of the line.
+        let elsewhere = String::from("12");
+        let e = ParseError::at_token(0, text, &elsewhere, "");
+        assert_eq!(e.column, 1);
+    }
+
+    #[test]
+    fn snippet() {
+        let e = ParseError::new(9, 4, "up 1x", "invalid amount");
+        assert_eq!(e.snippet(), "10 | up 1x\n   |     ^");
+
+        let e = ParseError::new(0, 0, "", "expected a command");
+        assert_eq!(e.snippet(), "1 | \n  | ^");
+    }
+
+    #[test]
+    fn display() {
+        let e = ParseError::new(1, 2, "abc", "oops");
+        assert_eq!(e.to_string(), "<input>:2:3: oops");
+        assert_eq!(e.in_file("small.txt").to_string(), "small.txt:2:3: oops");
+    }
+
+    #[test]
+    fn locate() {
+        let report = eyre::Report::new(ParseError::new(0, 1, "abc", "oops"));
+        let report = ParseError::locate(report, "inputs/2021/day1/small.txt");
+        let e = report.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(e.file.as_deref(), Some("inputs/2021/day1/small.txt"));
+
+        let report = ParseError::locate(eyre::eyre!("not a parse error"), "small.txt");
+        assert!(report.downcast_ref::<ParseError>().is_none());
+        assert_eq!(report.to_string(), "not a parse error");
+    }
+}
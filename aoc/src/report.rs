@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use color_eyre::eyre::{self, WrapErr};
+use serde::Serialize;
+
+use crate::bench::format_nanos;
+use crate::history::{current_commit, Run, RunKey, RunOutcome};
+use crate::{Answer, MAIN_VARIANT};
+
+/// The latest result of a part, as published.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub part: usize,
+    pub input: String,
+    pub outcome: RunOutcome,
+    pub answer: Option<Answer>,
+    pub duration: Option<Duration>,
+    pub commit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayReport {
+    pub day: usize,
+    pub title: String,
+    /// Parts that were never run are left out.
+    pub parts: Vec<PartReport>,
+}
+
+/// The latest results of the main solvers of every implemented day of a
+/// year.
+///
+/// A report publishes one answer per part, as the puzzle page would: that of
+/// the main solver on the real input. The examples only check the solvers,
+/// and the other variants are checked against the main one by `aoc check`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub year: u16,
+    pub commit: Option<String>,
+    pub generated: SystemTime,
+    pub days: Vec<DayReport>,
+}
+
+impl Report {
+    /// Picks, for each part, the latest run on the last input of the day
+    /// that was run, which is the real input unless it never was.
+    pub fn new(year: u16, runs: &HashMap<RunKey, Run>) -> Self {
+        let days = crate::days(year)
+            .into_iter()
+            .filter_map(|(number, day)| {
+                let day = day?;
+
+                let parts = (1..=2)
+                    .filter_map(|part| {
+                        let run = day.inputs().iter().rev().find_map(|input| {
+                            let key = (
+                                year,
+                                number,
+                                part,
+                                MAIN_VARIANT.to_string(),
+                                input.to_string(),
+                            );
+                            runs.get(&key)
+                        })?;
+
+                        Some(PartReport {
+                            part,
+                            input: run.input.clone(),
+                            outcome: run.outcome,
+                            answer: run.answer.clone(),
+                            duration: run.duration,
+                            commit: run.commit.clone(),
+                        })
+                    })
+                    .collect();
+
+                Some(DayReport {
+                    day: number,
+                    title: day.title(),
+                    parts,
+                })
+            })
+            .collect();
+
+        Self {
+            year,
+            commit: current_commit(),
+            generated: SystemTime::now(),
+            days,
+        }
+    }
+
+    /// A table with a row per day, and the answer and time of each part,
+    /// from the input picked by [`Report::new`].
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from(
+            "| Day | Title | Part 1 | Time | Part 2 | Time |\n\
+             |----:|-------|--------|-----:|--------|-----:|\n",
+        );
+
+        for day in self.days.iter() {
+            let mut cells = vec![day.day.to_string(), escape(&day.title)];
+
+            for part in 1..=2 {
+                let report = day.parts.iter().find(|p| p.part == part);
+
+                let answer = match report {
+                    Some(p) => match (&p.answer, p.outcome) {
+                        (Some(a), _) => format!("`{}`", escape(&a.summary())),
+                        (None, RunOutcome::Error) => "*error*".to_string(),
+                        (None, RunOutcome::TimedOut) => "*timed out*".to_string(),
+                        (None, _) => String::new(),
+                    },
+                    None => String::new(),
+                };
+                let time = report
+                    .and_then(|p| p.duration)
+                    .map(|d| format_nanos(d.as_nanos() as f64))
+                    .unwrap_or_default();
+
+                cells.push(answer);
+                cells.push(time);
+            }
+
+            table.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+
+        table
+    }
+
+    pub fn to_json(&self) -> eyre::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Writes the Markdown and JSON reports next to each other, as `path`
+    /// with the `md` and `json` extensions.
+    pub fn write(&self, path: &Path) -> eyre::Result<(PathBuf, PathBuf)> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .wrap_err_with(|| format!("Could not create {}", dir.display()))?;
+        }
+
+        let markdown = path.with_extension("md");
+        fs::write(&markdown, self.to_markdown())
+            .wrap_err_with(|| format!("Could not write {}", markdown.display()))?;
+
+        let json = path.with_extension("json");
+        fs::write(&json, self.to_json()? + "\n")
+            .wrap_err_with(|| format!("Could not write {}", json.display()))?;
+
+        Ok((markdown, json))
+    }
+}
+
+/// Keeps pipes from breaking table cells.
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Solution, Verdict};
+
+    fn run(part: usize, variant: &str, input: &str, answer: i128, millis: u64) -> Run {
+        let solution = Solution {
+            answer: Answer::Integer(answer),
+            verdict: Verdict::Unknown,
+            parse: Duration::ZERO,
+            duration: Duration::from_millis(millis),
+        };
+
+        Run::new(2021, 1, part, variant, input, &Ok(solution))
+    }
+
+    fn report(days: Vec<DayReport>) -> Report {
+        Report {
+            year: 2021,
+            commit: None,
+            generated: SystemTime::UNIX_EPOCH,
+            days,
+        }
+    }
+
+    fn part(part: usize, outcome: RunOutcome, answer: Option<Answer>) -> PartReport {
+        PartReport {
+            part,
+            input: String::from("inputs/2021/day1/real.txt"),
+            outcome,
+            answer,
+            duration: Some(Duration::from_micros(1500)),
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn reports_pick_the_main_solver_on_the_last_input() {
+        let (_, day) = crate::days(2021).into_iter().next().unwrap();
+        let day = day.unwrap();
+        let inputs = day.inputs();
+        let (small, real) = (inputs[0], inputs[inputs.len() - 1]);
+
+        let runs = [
+            run(1, MAIN_VARIANT, small, 7, 1),
+            run(1, MAIN_VARIANT, real, 1400, 2),
+            run(1, "slow", real, 1401, 3),
+            run(2, MAIN_VARIANT, small, 5, 4),
+        ];
+        let runs = runs.into_iter().map(|r| (r.key(), r)).collect();
+
+        let report = Report::new(2021, &runs);
+        let day = &report.days[0];
+
+        assert_eq!(day.day, 1);
+        assert_eq!(day.parts.len(), 2);
+        assert_eq!(day.parts[0].input, real);
+        assert_eq!(day.parts[0].answer, Some(Answer::Integer(1400)));
+        // Part 2 never ran on the real input.
+        assert_eq!(day.parts[1].input, small);
+        assert_eq!(day.parts[1].answer, Some(Answer::Integer(5)));
+
+        // Days that never ran are kept, without parts.
+        assert!(report.days[1..].iter().all(|d| d.parts.is_empty()));
+    }
+
+    #[test]
+    fn reports_render_as_a_table() {
+        let report = report(vec![
+            DayReport {
+                day: 1,
+                title: String::from("Sonar Sweep"),
+                parts: vec![
+                    part(1, RunOutcome::Finished, Some(Answer::Integer(1400))),
+                    part(2, RunOutcome::Error, None),
+                ],
+            },
+            DayReport {
+                day: 2,
+                title: String::from("Pipes | Tubes"),
+                parts: vec![part(
+                    2,
+                    RunOutcome::Finished,
+                    Some(Answer::Grid(vec![String::from("#."), String::from(".#")])),
+                )],
+            },
+        ]);
+
+        assert_eq!(
+            report.to_markdown(),
+            "| Day | Title | Part 1 | Time | Part 2 | Time |\n\
+             |----:|-------|--------|-----:|--------|-----:|\n\
+             | 1 | Sonar Sweep | `1400` | 1.500 ms | *error* | 1.500 ms |\n\
+             | 2 | Pipes \\| Tubes |  |  | `<grid 2x2>` | 1.500 ms |\n"
+        );
+    }
+}
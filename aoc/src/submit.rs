@@ -0,0 +1,649 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use color_eyre::eyre::{self, WrapErr};
+use color_eyre::Help;
+
+use crate::fetch::{FetchConfig, HttpClient, UreqClient};
+use crate::Answer;
+
+/// Name of the submission log in the data directory.
+pub const SUBMISSIONS: &str = "submissions.tsv";
+
+/// What the website made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the previous answer.
+    RateLimited,
+    /// The part was solved already, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+impl SubmitOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SubmitOutcome::Correct => "correct",
+            SubmitOutcome::Wrong => "wrong",
+            SubmitOutcome::TooHigh => "too-high",
+            SubmitOutcome::TooLow => "too-low",
+            SubmitOutcome::RateLimited => "rate-limited",
+            SubmitOutcome::AlreadySolved => "already-solved",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(SubmitOutcome::Correct),
+            "wrong" => Some(SubmitOutcome::Wrong),
+            "too-high" => Some(SubmitOutcome::TooHigh),
+            "too-low" => Some(SubmitOutcome::TooLow),
+            "rate-limited" => Some(SubmitOutcome::RateLimited),
+            "already-solved" => Some(SubmitOutcome::AlreadySolved),
+            _ => None,
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            SubmitOutcome::Wrong | SubmitOutcome::TooHigh | SubmitOutcome::TooLow
+        )
+    }
+
+    /// Reads the page the website answers a submission with.
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            Some(SubmitOutcome::Correct)
+        } else if page.contains("your answer is too high") {
+            Some(SubmitOutcome::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Some(SubmitOutcome::TooLow)
+        } else if page.contains("That's not the right answer") {
+            Some(SubmitOutcome::Wrong)
+        } else if page.contains("You gave an answer too recently") {
+            Some(SubmitOutcome::RateLimited)
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(SubmitOutcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::Wrong => write!(f, "wrong"),
+            SubmitOutcome::TooHigh => write!(f, "too high"),
+            SubmitOutcome::TooLow => write!(f, "too low"),
+            SubmitOutcome::RateLimited => write!(f, "rate limited"),
+            SubmitOutcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// A past submission, as kept in the submission log.
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub year: u16,
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+    pub at: SystemTime,
+    /// How long the website asked to wait before the next answer.
+    pub wait: Option<Duration>,
+}
+
+impl Submission {
+    /// One tab-separated line; the answer goes last as it is free text.
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            self.at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            self.wait.map(|w| w.as_secs()).unwrap_or(0),
+            self.outcome.as_str(),
+            self.answer
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(7, '\t');
+        let mut next = || fields.next();
+
+        let year = next()?.parse().ok()?;
+        let day = next()?.parse().ok()?;
+        let part = next()?.parse().ok()?;
+        let at = UNIX_EPOCH + Duration::from_secs(next()?.parse().ok()?);
+        let wait = Some(Duration::from_secs(next()?.parse().ok()?)).filter(|w| !w.is_zero());
+        let outcome = SubmitOutcome::from_str(next()?)?;
+        let answer = next()?.to_string();
+
+        Some(Self {
+            year,
+            day,
+            part,
+            answer,
+            outcome,
+            at,
+            wait,
+        })
+    }
+
+    /// Time left before another answer may be sent, if any.
+    fn cooldown(&self) -> Option<Duration> {
+        let until = self.at + self.wait?;
+        until.duration_since(SystemTime::now()).ok()
+    }
+}
+
+/// What became of a submission, and why.
+#[derive(Debug, Clone)]
+pub struct Receipt {
+    pub outcome: SubmitOutcome,
+    pub message: String,
+    /// Whether the outcome was worked out from past submissions, without
+    /// asking the website.
+    pub local: bool,
+}
+
+impl fmt::Display for Receipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.local {
+            write!(f, "{} (not sent: {})", self.outcome, self.message)
+        } else {
+            write!(f, "{}: {}", self.outcome, self.message)
+        }
+    }
+}
+
+/// Posts answers to the website, and keeps track of them so that the same
+/// wrong answer is never sent twice.
+pub struct Submitter {
+    config: FetchConfig,
+    client: Box<dyn HttpClient>,
+}
+
+impl Submitter {
+    pub fn new(config: FetchConfig) -> Self {
+        Self::with_client(config, UreqClient::new())
+    }
+
+    pub fn with_client<C>(config: FetchConfig, client: C) -> Self
+    where
+        C: HttpClient + 'static,
+    {
+        Self {
+            config,
+            client: Box::new(client),
+        }
+    }
+
+    pub fn url(&self, year: u16, day: usize) -> String {
+        format!(
+            "{}/{}/day/{}/answer",
+            self.config.base_url.trim_end_matches('/'),
+            year,
+            day
+        )
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.config.data_dir.join(SUBMISSIONS)
+    }
+
+    /// Every submission made so far, oldest first.
+    pub fn history(&self) -> eyre::Result<Vec<Submission>> {
+        let path = self.log_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let log = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+
+        Ok(log.lines().filter_map(Submission::from_line).collect())
+    }
+
+    fn record(&self, submission: &Submission) -> eyre::Result<()> {
+        let path = self.log_path();
+        fs::create_dir_all(&self.config.data_dir)
+            .wrap_err_with(|| format!("Could not create {}", self.config.data_dir.display()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .wrap_err_with(|| format!("Could not open {}", path.display()))?;
+
+        writeln!(file, "{}", submission.to_line())?;
+        Ok(())
+    }
+
+    /// The outcome of an answer as far as past submissions tell, if they are
+    /// enough to know it without sending it.
+    pub fn check(
+        &self,
+        year: u16,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> eyre::Result<Option<Receipt>> {
+        let past = self
+            .history()?
+            .into_iter()
+            .filter(|s| s.year == year && s.day == day && s.part == part)
+            .collect::<Vec<_>>();
+
+        let local = |outcome, message: String| {
+            Some(Receipt {
+                outcome,
+                message,
+                local: true,
+            })
+        };
+
+        if let Some(s) = past.iter().find(|s| s.outcome == SubmitOutcome::Correct) {
+            return Ok(if s.answer == answer {
+                local(SubmitOutcome::Correct, "already accepted".to_string())
+            } else {
+                local(
+                    SubmitOutcome::Wrong,
+                    format!("the accepted answer is {}", s.answer),
+                )
+            });
+        }
+
+        if let Some(s) = past
+            .iter()
+            .find(|s| s.answer == answer && s.outcome.is_wrong())
+        {
+            return Ok(local(s.outcome, "already submitted".to_string()));
+        }
+
+        if let Ok(n) = answer.parse::<i128>() {
+            let bound = |outcome| {
+                past.iter()
+                    .filter(move |s| s.outcome == outcome)
+                    .filter_map(|s| s.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(SubmitOutcome::TooHigh).find(|h| n >= *h) {
+                return Ok(local(
+                    SubmitOutcome::TooHigh,
+                    format!("{} was too high", high),
+                ));
+            }
+            if let Some(low) = bound(SubmitOutcome::TooLow).find(|l| n <= *l) {
+                return Ok(local(SubmitOutcome::TooLow, format!("{} was too low", low)));
+            }
+        }
+
+        if let Some(wait) = past.last().and_then(Submission::cooldown) {
+            return Ok(local(
+                SubmitOutcome::RateLimited,
+                format!("{}s left to wait", wait.as_secs() + 1),
+            ));
+        }
+
+        Ok(None)
+    }
+
+    /// Submits an answer, unless past submissions already tell its outcome.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: usize,
+        part: usize,
+        answer: &Answer,
+    ) -> eyre::Result<Receipt> {
+        let answer = match answer {
+            Answer::Grid(_) => {
+                return Err(eyre::eyre!("Grid answers can't be submitted as they are")
+                    .suggestion("Read the letters off the grid and submit them by hand"))
+            }
+            a => a.to_string(),
+        };
+
+        if let Some(receipt) = self.check(year, day, part, &answer)? {
+            return Ok(receipt);
+        }
+
+        let session = self.config.session.as_deref().ok_or_else(|| {
+            eyre::eyre!("No session token to submit the answer of day {}", day)
+                .suggestion("Set AOC_SESSION or pass --session")
+        })?;
+
+        let url = self.url(year, day);
+        let level = part.to_string();
+        let page = self
+            .client
+            .post(&url, session, &[("level", &level), ("answer", &answer)])
+            .wrap_err_with(|| {
+                format!("Could not submit the answer of day {} part {}", day, part)
+            })?;
+
+        let message = article_text(&page);
+        let outcome = SubmitOutcome::parse(&page).ok_or_else(|| {
+            eyre::eyre!("Unexpected answer from {}", url).section(message.clone())
+        })?;
+
+        self.record(&Submission {
+            year,
+            day,
+            part,
+            answer,
+            outcome,
+            at: SystemTime::now(),
+            wait: parse_wait(&message),
+        })?;
+
+        Ok(Receipt {
+            outcome,
+            message,
+            local: false,
+        })
+    }
+}
+
+/// The text of the `<article>` of a page, without its markup.
+fn article_text(page: &str) -> String {
+    let article = page
+        .find("<article>")
+        .and_then(|start| {
+            let end = page[start..].find("</article>")?;
+            Some(&page[start..start + end])
+        })
+        .unwrap_or(page);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads how long the website asks to wait, from messages such as "You have
+/// 4m 32s left to wait" or "please wait one minute before trying again".
+fn parse_wait(message: &str) -> Option<Duration> {
+    let words = message
+        .split(|c: char| c.is_whitespace() || c == '.' || c == ',' || c == ';')
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>();
+
+    let number = |s: &str| match s {
+        "one" | "a" | "an" => Some(1),
+        "five" => Some(5),
+        "ten" => Some(10),
+        s => s.parse::<u64>().ok(),
+    };
+
+    // The length of the duration written from the start of `words`, in
+    // seconds, and the number of words it takes.
+    let duration = |words: &[&str]| {
+        let mut total = 0;
+        let mut i = 0;
+        while i < words.len() {
+            let word = words[i];
+            if let Some(s) = word.strip_suffix('s').and_then(|s| s.parse::<u64>().ok()) {
+                total += s;
+                i += 1;
+            } else if let Some(m) = word.strip_suffix('m').and_then(|m| m.parse::<u64>().ok()) {
+                total += m * 60;
+                i += 1;
+            } else {
+                match (number(word), words.get(i + 1)) {
+                    (Some(n), Some(u)) if u.starts_with("minute") => total += n * 60,
+                    (Some(n), Some(u)) if u.starts_with("second") => total += n,
+                    _ => break,
+                }
+                i += 2;
+            }
+        }
+        total
+    };
+
+    (0..words.len())
+        .map(|i| duration(&words[i..]))
+        .find(|t| *t > 0)
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::{config, temp_dir, FakeClient, TempDir};
+
+    const RIGHT: &str = "<main><article><p>That's the right answer! You are \
+        <span class=\"day-success\">one gold star</span> closer to finding the \
+        sleigh keys.</p></article></main>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer \
+        is too high. Please wait one minute before trying again.</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer \
+        is too low. Please wait one minute before trying again.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer. Please wait \
+        one minute before trying again.</p></article>";
+    const TOO_RECENTLY: &str = "<article><p>You gave an answer too recently; you \
+        have to wait after submitting an answer before trying again. You have \
+        4m 32s left to wait.</p></article>";
+    const COMPLETE: &str = "<article><p>You don't seem to be solving the right \
+        level. Did you already complete it?</p></article>";
+
+    fn submission(part: usize, answer: &str, outcome: SubmitOutcome) -> Submission {
+        Submission {
+            year: 2021,
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            at: UNIX_EPOCH + Duration::from_secs(1_638_334_800),
+            wait: None,
+        }
+    }
+
+    /// A submitter that made the given submissions, along with the directory
+    /// of its records.
+    fn submitter(history: &[Submission]) -> (TempDir, Submitter) {
+        let dir = temp_dir("submitter");
+        let submitter = Submitter::with_client(config(&dir, Some("abc")), FakeClient::new(""));
+        for s in history {
+            submitter.record(s).unwrap();
+        }
+        (dir, submitter)
+    }
+
+    fn check(submitter: &Submitter, answer: &str) -> Option<(SubmitOutcome, String)> {
+        let receipt = submitter.check(2021, 1, 1, answer).unwrap()?;
+        assert!(receipt.local);
+        Some((receipt.outcome, receipt.message))
+    }
+
+    #[test]
+    fn parse_outcomes() {
+        assert_eq!(SubmitOutcome::parse(RIGHT), Some(SubmitOutcome::Correct));
+        assert_eq!(SubmitOutcome::parse(TOO_HIGH), Some(SubmitOutcome::TooHigh));
+        assert_eq!(SubmitOutcome::parse(TOO_LOW), Some(SubmitOutcome::TooLow));
+        assert_eq!(SubmitOutcome::parse(WRONG), Some(SubmitOutcome::Wrong));
+        assert_eq!(
+            SubmitOutcome::parse(TOO_RECENTLY),
+            Some(SubmitOutcome::RateLimited)
+        );
+        assert_eq!(
+            SubmitOutcome::parse(COMPLETE),
+            Some(SubmitOutcome::AlreadySolved)
+        );
+        assert_eq!(SubmitOutcome::parse("<html>Puzzle inputs</html>"), None);
+    }
+
+    #[test]
+    fn parse_waits() {
+        assert_eq!(
+            parse_wait("You have 4m 32s left to wait."),
+            Some(Duration::from_secs(272))
+        );
+        assert_eq!(
+            parse_wait("Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_wait(&article_text(TOO_RECENTLY)),
+            Some(Duration::from_secs(272))
+        );
+        assert_eq!(parse_wait(&article_text(RIGHT)), None);
+    }
+
+    #[test]
+    fn submissions_round_trip() {
+        let mut before = submission(2, "hello\tworld", SubmitOutcome::TooLow);
+        before.wait = Some(Duration::from_secs(60));
+
+        let after = Submission::from_line(&before.to_line()).unwrap();
+
+        assert_eq!(after.year, before.year);
+        assert_eq!(after.day, before.day);
+        assert_eq!(after.part, before.part);
+        assert_eq!(after.answer, before.answer);
+        assert_eq!(after.outcome, before.outcome);
+        assert_eq!(after.at, before.at);
+        assert_eq!(after.wait, before.wait);
+
+        let none = submission(1, "5", SubmitOutcome::Correct);
+        assert_eq!(Submission::from_line(&none.to_line()).unwrap().wait, None);
+        assert!(Submission::from_line("2021\t1\t1\tsoon").is_none());
+    }
+
+    #[test]
+    fn check_repeated_wrong_answer() {
+        let (_dir, submitter) = submitter(&[submission(1, "1234", SubmitOutcome::Wrong)]);
+
+        assert_eq!(
+            check(&submitter, "1234"),
+            Some((SubmitOutcome::Wrong, "already submitted".to_string()))
+        );
+        assert_eq!(check(&submitter, "1235"), None);
+        // Only answers to the same part count.
+        assert_eq!(
+            submitter
+                .check(2021, 1, 2, "1234")
+                .unwrap()
+                .map(|r| r.outcome),
+            None
+        );
+    }
+
+    #[test]
+    fn check_bounds() {
+        let (_dir, submitter) = submitter(&[
+            submission(1, "2000", SubmitOutcome::TooHigh),
+            submission(1, "1000", SubmitOutcome::TooLow),
+        ]);
+
+        assert_eq!(
+            check(&submitter, "2500"),
+            Some((SubmitOutcome::TooHigh, "2000 was too high".to_string()))
+        );
+        assert_eq!(
+            check(&submitter, "999"),
+            Some((SubmitOutcome::TooLow, "1000 was too low".to_string()))
+        );
+        assert_eq!(
+            check(&submitter, "-3"),
+            Some((SubmitOutcome::TooLow, "1000 was too low".to_string()))
+        );
+        assert_eq!(check(&submitter, "1500"), None);
+        assert_eq!(check(&submitter, "abc"), None);
+    }
+
+    #[test]
+    fn check_accepted_part() {
+        let (_dir, submitter) = submitter(&[
+            submission(1, "7", SubmitOutcome::TooLow),
+            submission(1, "1502", SubmitOutcome::Correct),
+        ]);
+
+        assert_eq!(
+            check(&submitter, "1502"),
+            Some((SubmitOutcome::Correct, "already accepted".to_string()))
+        );
+        assert_eq!(
+            check(&submitter, "1501"),
+            Some((
+                SubmitOutcome::Wrong,
+                "the accepted answer is 1502".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn check_cooldown() {
+        let mut recent = submission(1, "1234", SubmitOutcome::Wrong);
+        recent.at = SystemTime::now();
+        recent.wait = Some(Duration::from_secs(300));
+        let (_dir, submitter) = submitter(&[recent]);
+
+        let (outcome, message) = check(&submitter, "1502").unwrap();
+        assert_eq!(outcome, SubmitOutcome::RateLimited);
+        assert!(message.ends_with("s left to wait"));
+
+        let mut old = submission(1, "1234", SubmitOutcome::Wrong);
+        old.wait = Some(Duration::from_secs(300));
+        let (_dir, submitter) = self::submitter(&[old]);
+        assert_eq!(check(&submitter, "1502"), None);
+    }
+
+    #[test]
+    fn submit_with_client() {
+        let dir = temp_dir("submit");
+        let client = FakeClient::new(TOO_HIGH);
+        let submitter = Submitter::with_client(config(&dir, Some("abc")), client.clone());
+
+        let receipt = submitter
+            .submit(2021, 1, 2, &Answer::Integer(1_000_000))
+            .unwrap();
+
+        assert_eq!(receipt.outcome, SubmitOutcome::TooHigh);
+        assert!(!receipt.local);
+        assert!(receipt.message.starts_with("That's not the right answer"));
+        assert_eq!(
+            client.requests(),
+            ["POST http://localhost:8080/2021/day/1/answer session=abc level=2&answer=1000000"]
+        );
+
+        let history = submitter.history().unwrap();
+        assert!(dir.join(".aoc").join(SUBMISSIONS).exists());
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].answer, "1000000");
+        assert_eq!(history[0].wait, Some(Duration::from_secs(60)));
+
+        // Known to be too high without asking again.
+        let receipt = submitter
+            .submit(2021, 1, 2, &Answer::Integer(1_000_001))
+            .unwrap();
+        assert_eq!(receipt.outcome, SubmitOutcome::TooHigh);
+        assert!(receipt.local);
+        assert_eq!(client.requests().len(), 1);
+    }
+}
@@ -1,12 +1,33 @@
+use std::collections::HashMap;
 use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use color_eyre::eyre;
+use itertools::Itertools;
 use tui::style::{Color, Modifier, Style};
 
-use crate::ui::{ThreadPool, WidgetList};
-use crate::AocDay;
+use crate::baseline::{Baseline, Comparison, DEFAULT_BASELINE, DEFAULT_THRESHOLD};
+use crate::bench::{BenchConfig, BenchReport};
+use crate::crosscheck::{self, CrossCheck};
+use crate::fetch::{FetchConfig, InputManager};
+use crate::history::{History, Run, RunKey, RunOutcome};
+use crate::hot::{self, BuildError};
+use crate::report::Report;
+use crate::submit::{Receipt, Submitter};
+use crate::ui::{
+    Change, Editor, InputView, Pane, PaneKind, Prompt, PromptAction, ThreadPool, Watcher,
+    WidgetList,
+};
+use crate::{
+    Answer, AocError, Context, DynDay, ParseError, Progress, Solution, Verdict, MAIN_VARIANT,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum State {
@@ -15,7 +36,7 @@ pub enum State {
     Input,
 }
 
-type ArcDay = Arc<Box<dyn AocDay + Sync + Send>>;
+type ArcDay = Arc<Box<dyn DynDay + Sync + Send>>;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum JobStatus {
@@ -23,6 +44,9 @@ pub enum JobStatus {
     Waiting,
     Running(usize),
     Finished(Duration),
+    Cancelled,
+    TimedOut,
+    Wrong(Duration),
     Error,
 }
 
@@ -34,11 +58,42 @@ impl fmt::Display for JobStatus {
             JobStatus::Running(id) => write!(f, "Running ({})", id),
             JobStatus::Error => write!(f, "Error"),
             JobStatus::Finished(_) => write!(f, "Finished"),
+            JobStatus::Cancelled => write!(f, "Cancelled"),
+            JobStatus::TimedOut => write!(f, "Timed out"),
+            JobStatus::Wrong(_) => write!(f, "Wrong answer"),
         }
     }
 }
 
+/// Directory, in the data directory, where the text of the editor is written
+/// to be run.
+const ADHOC: &str = "adhoc";
+
+/// Lines the editor moves by on Page Up and Page Down.
+const PAGE: usize = 10;
+
+pub(crate) const SPINNER: [char; 8] = ['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
+
+/// A text progress bar, `width` characters wide.
+fn gauge(fraction: f64, width: usize) -> String {
+    let filled = ((fraction * width as f64).round() as usize).min(width);
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
 impl JobStatus {
+    /// The status as shown in tables: running jobs get a gauge if their
+    /// progress is known from `fractions`, and a spinner otherwise.
+    pub fn text(&self, fractions: &[f64], tick: usize) -> String {
+        match self {
+            JobStatus::Running(_) if !fractions.is_empty() => {
+                let fraction = fractions.iter().sum::<f64>() / fractions.len() as f64;
+                format!("{} {:>3.0}%", gauge(fraction, 10), fraction * 100.0)
+            }
+            JobStatus::Running(_) => format!("{} {}", SPINNER[(tick / 4) % SPINNER.len()], self),
+            _ => self.to_string(),
+        }
+    }
+
     pub fn style(&self) -> Style {
         match self {
             JobStatus::Ready => Style::default(),
@@ -46,6 +101,9 @@ impl JobStatus {
             JobStatus::Running(_) => Style::default().fg(Color::Blue),
             JobStatus::Error => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             JobStatus::Finished(_) => Style::default().fg(Color::Green),
+            JobStatus::Cancelled => Style::default().fg(Color::DarkGray),
+            JobStatus::TimedOut => Style::default().fg(Color::Magenta),
+            JobStatus::Wrong(_) => Style::default().fg(Color::Red),
         }
     }
 }
@@ -53,6 +111,12 @@ impl JobStatus {
 pub struct OutputCommunication {
     output: (Sender<String>, Receiver<String>),
     debug: (Sender<String>, Receiver<String>),
+    bench: (Sender<BenchReport>, Receiver<BenchReport>),
+    progress: (Sender<Progress>, Receiver<Progress>),
+    receipt: (
+        Sender<color_eyre::Result<Receipt>>,
+        Receiver<color_eyre::Result<Receipt>>,
+    ),
 }
 
 impl OutputCommunication {
@@ -60,68 +124,211 @@ impl OutputCommunication {
         Self {
             output: mpsc::channel(),
             debug: mpsc::channel(),
+            bench: mpsc::channel(),
+            progress: mpsc::channel(),
+            receipt: mpsc::channel(),
         }
     }
 
-    pub fn senders(&self) -> (Sender<String>, Sender<String>) {
-        (self.output.0.clone(), self.debug.0.clone())
+    pub fn context(&self) -> Context {
+        Context::new(self.output.0.clone(), self.debug.0.clone())
+            .with_progress(self.progress.0.clone())
     }
 
+    /// Everything printed since the last call, as long traces would take
+    /// ages to show a message per tick.
     pub fn output(&self) -> Option<String> {
-        self.output.1.try_recv().ok()
+        Some(self.output.1.try_iter().collect::<String>()).filter(|s| !s.is_empty())
     }
 
     pub fn debug(&self) -> Option<String> {
-        self.debug.1.try_recv().ok()
+        Some(self.debug.1.try_iter().collect::<String>()).filter(|s| !s.is_empty())
+    }
+
+    pub fn bench_sender(&self) -> Sender<BenchReport> {
+        self.bench.0.clone()
+    }
+
+    pub fn bench(&self) -> Option<BenchReport> {
+        self.bench.1.try_recv().ok()
+    }
+
+    /// The latest progress report, skipping the ones that piled up since the
+    /// last call.
+    pub fn progress(&self) -> Option<Progress> {
+        self.progress.1.try_iter().last()
+    }
+
+    pub fn receipt_sender(&self) -> Sender<color_eyre::Result<Receipt>> {
+        self.receipt.0.clone()
+    }
+
+    pub fn receipt(&self) -> Option<color_eyre::Result<Receipt>> {
+        self.receipt.1.try_recv().ok()
     }
 }
 
 pub struct Instance {
     pub(crate) input: &'static str,
     pub(crate) part: usize,
+    pub(crate) variant: &'static str,
     pub(crate) job_id: Option<usize>,
     pub(crate) status: JobStatus,
     pub(crate) communication: OutputCommunication,
+    pub(crate) answer: Option<Answer>,
+    pub(crate) verdict: Option<Verdict>,
+    pub(crate) bench: Option<BenchReport>,
+    pub(crate) progress: Option<Progress>,
+    pub(crate) started: Option<Instant>,
+    pub(crate) receipt: Option<Receipt>,
+    pub(crate) submitting: bool,
     pub(crate) output: String,
     pub(crate) debug: String,
+    /// The input file as shown in the viewer, read when first shown. Reading
+    /// it again is left to the next run.
+    pub(crate) view: Option<Result<InputView, String>>,
+    /// Whether the input was typed in the editor, rather than one of the day.
+    pub(crate) temporary: bool,
+    /// Whether the last job was a benchmark, whose median isn't a run.
+    pub(crate) benching: bool,
 }
 
 impl Instance {
-    pub fn new(input: &'static str, part: usize) -> Self {
+    pub fn new(input: &'static str, part: usize, variant: &'static str) -> Self {
         Instance {
             input,
             part,
+            variant,
             job_id: None,
             status: JobStatus::Ready,
             communication: OutputCommunication::new(),
+            answer: None,
+            verdict: None,
+            bench: None,
+            progress: None,
+            started: None,
+            receipt: None,
+            submitting: false,
             output: String::new(),
             debug: String::new(),
+            view: None,
+            temporary: false,
+            benching: false,
         }
     }
 
     pub fn clear(&mut self) {
+        self.answer = None;
+        self.verdict = None;
+        self.bench = None;
+        self.progress = None;
+        self.receipt = None;
         self.output.clear();
         self.debug.clear();
+        self.view = None;
+        self.benching = false;
     }
 
-    pub fn duration(&self) -> Option<String> {
-        if let JobStatus::Finished(d) = &self.status {
-            Some(format!(
-                "{:02}:{:02}.{:03}",
-                d.as_secs() / 60,
-                d.as_secs() % 60,
-                d.as_millis() % 1000
-            ))
+    /// Takes the status and answer of a past run of the instance.
+    fn restore(&mut self, run: &Run, expected: Option<Answer>) {
+        let duration = run.duration.unwrap_or_default();
+
+        self.answer = run.answer.clone();
+        self.verdict = self.answer.as_ref().map(|a| Verdict::new(a, expected));
+        self.status = match run.outcome {
+            RunOutcome::Finished => JobStatus::Finished(duration),
+            RunOutcome::Wrong => JobStatus::Wrong(duration),
+            RunOutcome::Error => JobStatus::Error,
+            RunOutcome::Cancelled => JobStatus::Cancelled,
+            RunOutcome::TimedOut => JobStatus::TimedOut,
+        };
+
+        if let Some(e) = &run.error {
+            self.debug = format!("Error: {}\n", e);
+        }
+    }
+
+    /// How long the part took to solve, once it finished.
+    pub fn elapsed(&self) -> Option<Duration> {
+        if let JobStatus::Finished(d) | JobStatus::Wrong(d) = self.status {
+            Some(d)
         } else {
             None
         }
     }
 
+    pub fn duration(&self) -> Option<String> {
+        self.elapsed().map(|d| crate::format_duration(&d))
+    }
+
+    pub fn answer_summary(&self) -> String {
+        match (&self.answer, &self.verdict) {
+            (Some(a), Some(Verdict::Pass)) => format!("{} (pass)", a.summary()),
+            (Some(a), Some(Verdict::Fail(e))) => {
+                format!("{} (expected {})", a.summary(), e.summary())
+            }
+            (Some(a), _) => a.summary(),
+            (None, _) => self
+                .progress
+                .as_ref()
+                .map(|p| p.label.clone())
+                .unwrap_or_default(),
+        }
+    }
+
     pub fn update(&mut self) {
         self.output
-            .push_str(&self.communication.output().unwrap_or_else(String::new));
+            .push_str(&self.communication.output().unwrap_or_default());
         self.debug
-            .push_str(&self.communication.debug().unwrap_or_else(String::new));
+            .push_str(&self.communication.debug().unwrap_or_default());
+        if let Some(bench) = self.communication.bench() {
+            self.bench = Some(bench);
+        }
+
+        if let Some(receipt) = self.communication.receipt() {
+            self.submitting = false;
+            match receipt {
+                Ok(r) => self.receipt = Some(r),
+                Err(e) => self.debug.push_str(&describe_error(&e)),
+            }
+        }
+
+        if let JobStatus::Running(_) = self.status {
+            self.started.get_or_insert_with(Instant::now);
+            if let Some(progress) = self.communication.progress() {
+                self.progress = Some(progress);
+            }
+        } else {
+            self.started = None;
+            self.progress = None;
+        }
+    }
+
+    pub fn status_text(&self, tick: usize) -> String {
+        let fractions = self.progress.iter().map(|p| p.fraction).collect::<Vec<_>>();
+        let text = self.status.text(&fractions, tick);
+
+        match (&self.progress, self.started) {
+            (Some(p), Some(started)) if p.fraction > 0.0 && p.fraction < 1.0 => {
+                let eta = started.elapsed().mul_f64((1.0 - p.fraction) / p.fraction);
+                format!(
+                    "{} ETA {:02}:{:02}",
+                    text,
+                    eta.as_secs() / 60,
+                    eta.as_secs() % 60
+                )
+            }
+            _ => text,
+        }
+    }
+}
+
+/// Renders an error for the Debug pane, without the colours and backtrace
+/// that `color_eyre` would add.
+fn describe_error(e: &color_eyre::Report) -> String {
+    match e.downcast_ref::<ParseError>() {
+        Some(p) => format!("Error: {}\n{}\n", p, p.snippet()),
+        None => format!("Error: {:#}\n", e),
     }
 }
 
@@ -129,6 +336,15 @@ pub struct Day {
     pub(crate) number: usize,
     pub(crate) day: Option<ArcDay>,
     pub(crate) instances: Vec<Instance>,
+    /// Input typed or pasted in for the temporary instances.
+    pub(crate) editor: Editor,
+    /// Where the text of the editor is written to be run, once it is.
+    adhoc: Option<&'static str>,
+    /// What the variants of each part answered on each input, when the day
+    /// was last cross-checked.
+    pub(crate) checks: Vec<CrossCheck>,
+    /// The cross-checks still to come in.
+    cross_checks: Option<Receiver<CrossCheck>>,
 }
 
 impl From<crate::Day> for Day {
@@ -138,7 +354,13 @@ impl From<crate::Day> for Day {
         let instances = if let Some(b) = &b {
             b.inputs()
                 .iter()
-                .flat_map(|i| vec![Instance::new(i, 1), Instance::new(i, 2)])
+                .flat_map(|i| {
+                    (1..=2).flat_map(move |part| {
+                        b.variants(part)
+                            .into_iter()
+                            .map(move |v| Instance::new(i, part, v))
+                    })
+                })
                 .collect()
         } else {
             Vec::new()
@@ -148,11 +370,44 @@ impl From<crate::Day> for Day {
             number,
             day: b.map(Arc::from),
             instances,
+            editor: Editor::default(),
+            adhoc: None,
+            checks: Vec::new(),
+            cross_checks: None,
         }
     }
 }
 
 impl Day {
+    /// Takes the solutions of a reloaded day, keeping the instances that
+    /// still exist, along with their results and output, and the temporary
+    /// ones.
+    fn reload(&mut self, day: crate::Day) {
+        let mut reloaded = Day::from(day);
+
+        for instance in reloaded.instances.iter_mut() {
+            let kept = self.instances.iter().position(|i| {
+                i.input == instance.input
+                    && i.part == instance.part
+                    && i.variant == instance.variant
+            });
+
+            if let Some(n) = kept {
+                *instance = self.instances.remove(n);
+            }
+        }
+
+        if reloaded.day.is_some() {
+            let temporary = self.instances.drain(..).filter(|i| i.temporary);
+            reloaded.instances.extend(temporary);
+            reloaded.editor = std::mem::take(&mut self.editor);
+            reloaded.adhoc = self.adhoc;
+        }
+
+        *self = reloaded;
+    }
+
+    #[allow(dead_code)]
     pub fn is_present(&self) -> bool {
         self.day.is_some()
     }
@@ -161,6 +416,169 @@ impl Day {
         self.instances.iter().map(|i| i.status).max().unwrap()
     }
 
+    /// Names of the solvers of a part, the main one first.
+    pub fn variants(&self, part: usize) -> Vec<&'static str> {
+        self.instances
+            .iter()
+            .filter(|i| i.part == part)
+            .map(|i| i.variant)
+            .unique()
+            .collect()
+    }
+
+    pub fn status_for_variant(&self, part: usize, variant: &str) -> JobStatus {
+        self.instances
+            .iter()
+            .filter(|i| i.part == part && i.variant == variant)
+            .map(|i| i.status)
+            .max()
+            .unwrap()
+    }
+
+    /// The last cross-check of the part of an instance on its input.
+    pub fn check(&self, instance: &Instance) -> Option<&CrossCheck> {
+        self.checks
+            .iter()
+            .find(|c| c.input == instance.input && c.part == instance.part)
+    }
+
+    /// The answer of an instance, or else what its variant answered when the
+    /// day was cross-checked.
+    pub fn answer_summary(&self, instance: &Instance) -> String {
+        let checked = self
+            .check(instance)
+            .filter(|_| instance.answer.is_none())
+            .and_then(|c| c.results.iter().find(|(v, _)| *v == instance.variant));
+
+        match checked {
+            Some((_, Ok(answer))) => answer.summary(),
+            Some((_, Err(_))) => String::from("(error)"),
+            None => instance.answer_summary(),
+        }
+    }
+
+    /// Whether the variants of the part of an instance disagree on its input,
+    /// once it is done, as [`Answer::matches`] compares answers. A variant that
+    /// failed disagrees with the ones that found an answer. A cross-check that
+    /// found them disagreeing counts too.
+    pub fn mismatch(&self, instance: &Instance) -> bool {
+        if self.check(instance).is_some_and(|c| !c.agrees()) {
+            return true;
+        }
+
+        let done = |i: &Instance| i.answer.is_some() || i.status == JobStatus::Error;
+
+        if !done(instance) {
+            return false;
+        }
+
+        let mut answers = self
+            .instances
+            .iter()
+            .filter(|i| i.input == instance.input && i.part == instance.part)
+            .filter(|i| done(i))
+            .map(|i| i.answer.as_ref());
+
+        match answers.next() {
+            Some(Some(first)) => !answers.all(|a| matches!(a, Some(a) if a.matches(first))),
+            Some(None) => answers.any(|a| a.is_some()),
+            None => false,
+        }
+    }
+
+    /// Instances of a variant on the inputs of the day, for one part or for
+    /// both.
+    fn instances_of<'a>(
+        &'a self,
+        part: Option<usize>,
+        variant: &'a str,
+    ) -> impl Iterator<Item = &'a Instance> {
+        self.instances.iter().filter(move |i| {
+            part.is_none_or(|p| i.part == p) && i.variant == variant && !i.temporary
+        })
+    }
+
+    /// The instance of a variant whose answer stands for it: the one on
+    /// `input`, or else the last one with an answer, from a run or a
+    /// cross-check.
+    pub fn answering<'a>(
+        &'a self,
+        part: usize,
+        variant: &'a str,
+        input: Option<&str>,
+    ) -> Option<&'a Instance> {
+        match input {
+            Some(input) => self
+                .instances
+                .iter()
+                .find(|i| i.part == part && i.variant == variant && i.input == input),
+            None => self
+                .instances_of(Some(part), variant)
+                .filter(|i| i.answer.is_some() || self.check(i).is_some())
+                .last(),
+        }
+    }
+
+    /// Total solve time of a variant on every input, once it finished on any.
+    pub fn time(&self, part: Option<usize>, variant: &str) -> Option<Duration> {
+        self.instances_of(part, variant)
+            .filter_map(Instance::elapsed)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Takes the cross-checks that came in, keeping the errors of variants in
+    /// the Debug pane of their instance. Once the last one is in, returns how
+    /// they went.
+    fn update_checks(&mut self) -> Option<String> {
+        let receiver = self.cross_checks.as_ref()?;
+
+        loop {
+            match receiver.try_recv() {
+                Ok(check) => {
+                    for (variant, result) in check.results.iter() {
+                        let instance = self.instances.iter_mut().find(|i| {
+                            i.input == check.input && i.part == check.part && i.variant == *variant
+                        });
+
+                        if let (Some(i), Err(e)) = (instance, result) {
+                            i.debug.push_str(&format!("Error: {}\n", e));
+                        }
+                    }
+
+                    self.checks
+                        .retain(|c| c.input != check.input || c.part != check.part);
+                    self.checks.push(check);
+                }
+                Err(mpsc::TryRecvError::Empty) => return None,
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+
+        self.cross_checks = None;
+        let disagreeing = self.checks.iter().filter(|c| !c.agrees()).count();
+
+        Some(match disagreeing {
+            _ if self.checks.is_empty() => {
+                format!("Day {}: no input to cross-check", self.number)
+            }
+            0 => format!("Day {}: the variants agree on every input", self.number),
+            n => format!(
+                "Day {}: the variants disagree on {} of {} parts and inputs",
+                self.number,
+                n,
+                self.checks.len()
+            ),
+        })
+    }
+
+    /// Whether the variants of a part disagree on any of its inputs.
+    pub fn disagrees(&self, part: usize) -> bool {
+        self.instances
+            .iter()
+            .filter(|i| i.part == part)
+            .any(|i| self.mismatch(i))
+    }
+
     pub fn status_for_part(&self, part: usize) -> JobStatus {
         self.instances
             .iter()
@@ -169,37 +587,454 @@ impl Day {
             .max()
             .unwrap()
     }
+
+    /// The status text of the day, or of one of its parts, with the average
+    /// progress of its running instances.
+    pub fn status_text(&self, part: Option<usize>, tick: usize) -> String {
+        let status = match part {
+            Some(p) => self.status_for_part(p),
+            None => self.status(),
+        };
+
+        let fractions = self
+            .instances
+            .iter()
+            .filter(|i| part.map(|p| i.part == p).unwrap_or(true))
+            .filter(|i| matches!(i.status, JobStatus::Running(_)))
+            .filter_map(|i| i.progress.as_ref().map(|p| p.fraction))
+            .collect::<Vec<_>>();
+
+        status.text(&fractions, tick)
+    }
 }
 
-pub struct App {
+/// The days of one event.
+pub struct Year {
+    pub(crate) number: u16,
     pub(crate) days: Vec<Day>,
+}
+
+impl Year {
+    pub fn new(number: u16) -> Self {
+        Self {
+            number,
+            days: crate::days(number).into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Takes the solutions of every day from the library loaded last.
+    fn reload(&mut self) {
+        for (day, reloaded) in self.days.iter_mut().zip(crate::days(self.number)) {
+            day.reload(reloaded);
+        }
+    }
+
+    /// Restores each instance to its last recorded run.
+    fn restore(&mut self, runs: &HashMap<RunKey, Run>) {
+        for day in self.days.iter_mut() {
+            let solutions = match &day.day {
+                Some(d) => d.clone(),
+                None => continue,
+            };
+
+            for i in day.instances.iter_mut() {
+                let key = (
+                    self.number,
+                    day.number,
+                    i.part,
+                    i.variant.to_string(),
+                    i.input.to_string(),
+                );
+
+                if let Some(run) = runs.get(&key) {
+                    i.restore(run, solutions.expected(i.input, i.part));
+                }
+            }
+        }
+    }
+}
+
+/// Settings of the TUI that come from the command line.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Wall-clock time after which a run is cancelled.
+    pub timeout: Option<Duration>,
+    /// Year shown first; the latest one if `None`.
+    pub year: Option<u16>,
+    pub fetch: FetchConfig,
+    /// Baseline solve times are compared with; the default one if `None`.
+    pub baseline: Option<String>,
+    /// Slowdown beyond which a solve time counts as a regression; the
+    /// default one if `None`.
+    pub threshold: Option<f64>,
+    /// Whether instances are run again when their input changes.
+    pub watch: bool,
+    /// Where to pick up after the TUI restarted on a rebuild.
+    pub resume: Option<Resume>,
+}
+
+/// The selection of the TUI, kept over a restart as `year:day:part:input`
+/// with `-` for what isn't selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resume {
+    pub year: u16,
+    pub day: Option<usize>,
+    pub part: Option<usize>,
+    pub input: Option<usize>,
+}
+
+impl fmt::Display for Resume {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = |v: Option<usize>| v.map_or("-".to_string(), |v| v.to_string());
+
+        write!(
+            f,
+            "{}:{}:{}:{}",
+            self.year,
+            field(self.day),
+            field(self.part),
+            field(self.input)
+        )
+    }
+}
+
+impl FromStr for Resume {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let field = |v: &str| -> eyre::Result<Option<usize>> {
+            match v {
+                "-" => Ok(None),
+                v => Ok(Some(v.parse()?)),
+            }
+        };
+
+        match s.split(':').collect::<Vec<_>>()[..] {
+            [year, day, part, input] => Ok(Self {
+                year: year.parse()?,
+                day: field(day)?,
+                part: field(part)?,
+                input: field(input)?,
+            }),
+            _ => Err(eyre::eyre!("Invalid selection to resume: {}", s)),
+        }
+    }
+}
+
+/// Why the TUI stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Quit,
+    /// The binary was rebuilt, and should be started again.
+    Restart(Resume),
+}
+
+pub struct App {
+    pub(crate) years: Vec<Year>,
+    pub(crate) year_highlight: usize,
     pub(crate) day_highlight: Option<usize>,
     pub(crate) part_highlight: Option<usize>,
     pub(crate) input_highlight: Option<usize>,
+    #[allow(dead_code)]
     widgets: WidgetList,
     pool: ThreadPool<4>,
     pub(crate) state: State,
+    pub(crate) tick: usize,
+    pub(crate) inputs: Arc<InputManager>,
+    submitter: Arc<Submitter>,
+    history: History,
+    baseline: Option<Baseline>,
+    pub(crate) prompt: Option<Prompt>,
+    /// Outcome of the last action that has no pane of its own, shown until
+    /// the next key.
+    pub(crate) notice: Option<String>,
+    pub(crate) output_pane: Pane,
+    pub(crate) debug_pane: Pane,
+    pub(crate) input_pane: Pane,
+    /// Pane that scrolling and searching apply to.
+    pub(crate) focus: PaneKind,
+    /// Whether the focused pane takes the whole screen.
+    pub(crate) maximised: bool,
+    pub(crate) watcher: Option<Watcher>,
+    restart: Option<Resume>,
+    /// The build of the solutions going on, to reload them once it's done.
+    pub(crate) build: Option<Receiver<eyre::Result<PathBuf>>>,
+    /// Compiler output of the last build, if it failed.
+    pub(crate) build_log: Option<String>,
+    pub(crate) build_pane: Pane,
+    /// Whether keys go to the editor of the highlighted day.
+    pub(crate) editing: bool,
+    options: Options,
     should_quit: bool,
 }
 
 impl App {
-    pub fn new() -> Self {
-        Self {
-            days: crate::days().into_iter().map(Into::into).collect(),
+    pub fn new(options: Options) -> Self {
+        let history = History::new(&options.fetch);
+        let runs = history.latest().unwrap_or_default();
+        let baseline = Baseline::load(
+            &options.fetch,
+            options.baseline.as_deref().unwrap_or(DEFAULT_BASELINE),
+        )
+        .ok();
+
+        let years = crate::YEARS
+            .iter()
+            .map(|y| {
+                let mut year = Year::new(*y);
+                year.restore(&runs);
+                year
+            })
+            .collect::<Vec<_>>();
+        let year_highlight = options
+            .resume
+            .map(|r| r.year)
+            .or(options.year)
+            .and_then(|y| years.iter().position(|year| year.number == y))
+            .unwrap_or(years.len() - 1);
+
+        let mut app = Self {
+            years,
+            year_highlight,
             day_highlight: Some(0),
             part_highlight: None,
             input_highlight: None,
             widgets: WidgetList::new(),
             pool: ThreadPool::new(),
             state: State::Day,
+            tick: 0,
+            inputs: Arc::new(InputManager::new(options.fetch.clone())),
+            submitter: Arc::new(Submitter::new(options.fetch.clone())),
+            history,
+            baseline,
+            prompt: None,
+            notice: None,
+            output_pane: Pane::default(),
+            debug_pane: Pane::default(),
+            input_pane: Pane::fixed(),
+            focus: PaneKind::Output,
+            maximised: false,
+            watcher: None,
+            restart: None,
+            build: None,
+            build_log: None,
+            build_pane: Pane::fixed(),
+            editing: false,
+            options,
             should_quit: false,
+        };
+
+        if app.options.watch || app.options.resume.is_some() {
+            app.watcher = Some(app.watch());
+        }
+
+        if let Some(resume) = app.options.resume {
+            app.resume(resume);
         }
+
+        app
+    }
+
+    /// Selects what was selected before a restart, and runs the day again
+    /// as its solutions were rebuilt.
+    fn resume(&mut self, resume: Resume) {
+        let day = match resume.day.filter(|d| *d < self.days().len()) {
+            Some(d) => d,
+            None => return,
+        };
+
+        self.day_highlight = Some(day);
+        if let Some(part) = resume.part.filter(|p| *p < 2) {
+            self.part_highlight = Some(part);
+            self.state = State::Part;
+
+            let inputs = self.days()[day]
+                .instances
+                .iter()
+                .filter(|i| i.part == part + 1)
+                .count();
+            if let Some(input) = resume.input.filter(|i| *i < inputs) {
+                self.input_highlight = Some(input);
+                self.state = State::Input;
+            }
+        }
+
+        self.run_instances(self.year_highlight, day, |_| true);
+    }
+
+    /// Starts watching every input, and the binary.
+    fn watch(&self) -> Watcher {
+        let inputs = self
+            .years
+            .iter()
+            .flat_map(|y| y.days.iter())
+            .flat_map(|d| d.instances.iter())
+            .filter(|i| !i.temporary)
+            .map(|i| i.input.to_string())
+            .unique()
+            .collect();
+
+        Watcher::new(inputs)
+    }
+
+    /// Runs again the instances whose input changed, and has the TUI restart
+    /// when the binary was rebuilt.
+    fn on_changes(&mut self, changes: Vec<Change>) {
+        for change in changes {
+            match change {
+                Change::Input(input) => {
+                    for year in 0..self.years.len() {
+                        for day in 0..self.years[year].days.len() {
+                            if self.years[year].days[day]
+                                .instances
+                                .iter()
+                                .any(|i| i.input == input)
+                            {
+                                self.run_instances(year, day, |i| i.input == input);
+                            }
+                        }
+                    }
+                    self.notice = Some(format!("{} changed, running it again", input));
+                }
+                Change::Binary => {
+                    self.restart = Some(Resume {
+                        year: self.year(),
+                        day: self.day_highlight,
+                        part: self.part_highlight,
+                        input: self.input_highlight,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Builds the solutions in the background, unless they are being built.
+    fn rebuild(&mut self) {
+        if self.build.is_some() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || tx.send(hot::build()));
+
+        self.build = Some(rx);
+        self.notice = Some(String::from("Rebuilding the solutions..."));
+    }
+
+    /// Loads the solutions once built, and swaps them in for those of every
+    /// day. A failed build leaves them be, and shows the compiler output.
+    fn on_build(&mut self, result: eyre::Result<PathBuf>) {
+        self.build = None;
+
+        if let Err(e) = result.and_then(|path| hot::load(&path)) {
+            self.build_log = Some(match e.downcast_ref::<BuildError>() {
+                Some(BuildError(output)) => output.clone(),
+                None => describe_error(&e),
+            });
+            self.build_pane = Pane::fixed();
+            self.focus = PaneKind::Build;
+            self.notice = Some(e.to_string());
+            return;
+        }
+
+        self.years.iter_mut().for_each(Year::reload);
+        self.build_log = None;
+        if self.focus == PaneKind::Build {
+            self.focus = PaneKind::Output;
+        }
+
+        if self.highlighted_instance().is_none() && self.input_highlight.is_some() {
+            self.input_highlight = None;
+            self.state = State::Part;
+        }
+        if self.watcher.is_some() {
+            self.watcher = Some(self.watch());
+        }
+        if let Some(day) = self.day_highlight {
+            self.run_instances(self.year_highlight, day, |_| true);
+        }
+
+        self.notice = Some(String::from("Reloaded the solutions"));
+    }
+
+    /// The selection to resume from, once the binary was rebuilt.
+    pub fn restart(&self) -> Option<Resume> {
+        self.restart
+    }
+
+    /// How the solve time of an instance compares to the baseline.
+    pub fn comparison(&self, day: &Day, instance: &Instance) -> Option<Comparison> {
+        let key = (
+            self.year(),
+            day.number,
+            instance.part,
+            instance.variant.to_string(),
+            instance.input.to_string(),
+        );
+
+        self.baseline.as_ref()?.compare(&key, instance.elapsed()?)
+    }
+
+    /// Whether an instance got slower than the baseline, beyond the
+    /// threshold.
+    pub fn regressed(&self, day: &Day, instance: &Instance) -> bool {
+        let threshold = self.options.threshold.unwrap_or(DEFAULT_THRESHOLD);
+
+        self.comparison(day, instance)
+            .is_some_and(|c| c.is_regression(threshold))
+    }
+
+    /// Whether a variant got slower than the baseline on any input.
+    pub fn slower(&self, day: &Day, part: Option<usize>, variant: &str) -> bool {
+        day.instances_of(part, variant)
+            .any(|i| self.regressed(day, i))
     }
 
     pub fn should_quit(&self) -> bool {
         self.should_quit
     }
 
+    /// The days of the year being shown.
+    pub fn days(&self) -> &[Day] {
+        &self.years[self.year_highlight].days
+    }
+
+    pub fn year(&self) -> u16 {
+        self.years[self.year_highlight].number
+    }
+
+    fn year_selection(&mut self, year_highlight: usize) {
+        self.year_highlight = year_highlight;
+        self.state = State::Day;
+        self.day_highlight = Some(0);
+        self.part_highlight = None;
+        self.input_highlight = None;
+    }
+
+    pub fn on_left(&mut self) {
+        if self.prompt.is_some() {
+            return;
+        }
+        if let Some(editor) = self.editor_mut() {
+            return editor.left();
+        }
+
+        let n = self.years.len();
+        self.year_selection((self.year_highlight + n - 1) % n);
+    }
+
+    pub fn on_right(&mut self) {
+        if self.prompt.is_some() {
+            return;
+        }
+        if let Some(editor) = self.editor_mut() {
+            return editor.right();
+        }
+
+        let n = self.years.len();
+        self.year_selection((self.year_highlight + 1) % n);
+    }
+
     fn day_selection(&mut self) {
         self.state = State::Day;
         self.part_highlight = None;
@@ -207,7 +1042,7 @@ impl App {
 
     fn input_selection(&mut self) {
         if let Some(n) = self.day_highlight {
-            if self.days.get(n).unwrap().day.is_some() {
+            if self.days().get(n).unwrap().day.is_some() {
                 self.state = State::Input;
                 self.input_highlight = Some(0);
             }
@@ -216,7 +1051,7 @@ impl App {
 
     fn part_selection(&mut self) {
         if let Some(n) = self.day_highlight {
-            if self.days.get(n).unwrap().day.is_some() {
+            if self.days().get(n).unwrap().day.is_some() {
                 self.state = State::Part;
                 self.part_highlight = Some(0);
                 self.input_highlight = None;
@@ -225,6 +1060,19 @@ impl App {
     }
 
     pub fn handle_key(&mut self, letter: char) {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.push(letter);
+            self.update_search();
+            return;
+        }
+
+        if let Some(editor) = self.editor_mut() {
+            editor.insert(letter);
+            return;
+        }
+
+        self.notice = None;
+
         match letter {
             'q' => match self.state {
                 State::Day => self.should_quit = true,
@@ -233,11 +1081,80 @@ impl App {
             },
             'Q' => self.should_quit = true,
             'R' => self.run_all(),
+            'r' => self.rebuild(),
+            'w' => {
+                self.watcher = match self.watcher.take() {
+                    Some(_) => None,
+                    None => Some(self.watch()),
+                }
+            }
+            '\t' => {
+                self.focus = self.focus.next();
+                // The build pane is only there while a failed build is shown.
+                if self.focus == PaneKind::Build && self.build_log.is_none() {
+                    self.focus = self.focus.next();
+                }
+            }
+            'k' => self.scroll(|p, lines| p.scroll_up(1, lines)),
+            'j' => self.scroll(|p, lines| p.scroll_down(1, lines)),
+            'h' => self.scroll(|p, _| p.scroll_left(4)),
+            'l' => {
+                let width = self.input_width();
+                self.scroll(|p, _| p.scroll_right(4, width))
+            }
+            'f' => {
+                let pane = self.pane_mut(self.focus);
+                pane.follow = !pane.follow;
+            }
+            'm' => self.maximised = !self.maximised,
+            '/' => {
+                let text = self.pane(self.focus).search.clone().unwrap_or_default();
+                self.prompt = Some(Prompt::new(
+                    format!("Search {}", self.focus.title()),
+                    text,
+                    PromptAction::Search(self.focus),
+                ))
+            }
+            'n' => self.find(true),
+            'N' => self.find(false),
+            'e' => {
+                self.prompt = Some(Prompt::new(
+                    "Export reports to (.md and .json)",
+                    "report",
+                    PromptAction::Export,
+                ))
+            }
+            'v' => self.cross_check(),
+            'i' if self.day_highlight.is_some_and(|d| self.is_day_present(d)) => {
+                self.editing = true
+            }
+            'b' => {
+                if let State::Input = self.state {
+                    self.bench_input()
+                }
+            }
+            'c' => {
+                if let State::Input = self.state {
+                    self.cancel_input()
+                }
+            }
+            's' => {
+                if let State::Input = self.state {
+                    self.submit_input()
+                }
+            }
             _ => {}
         }
     }
 
     pub fn on_up(&mut self) {
+        if self.prompt.is_some() {
+            return;
+        }
+        if let Some(editor) = self.editor_mut() {
+            return editor.up(1);
+        }
+
         match self.state {
             State::Day => {
                 if let Some(h) = self.day_highlight {
@@ -252,8 +1169,9 @@ impl App {
                 self.input_highlight = None;
             }
             State::Input => {
-                let day = self.days.get(self.day_highlight.unwrap()).unwrap();
-                let input_size = day.day.as_ref().map(|a| a.inputs().len()).unwrap_or(0);
+                let day = self.days().get(self.day_highlight.unwrap()).unwrap();
+                let part = self.part_highlight.unwrap() + 1;
+                let input_size = day.instances.iter().filter(|i| i.part == part).count();
                 if let Some(h) = self.input_highlight {
                     if h == 0 {
                         self.input_highlight = Some(input_size - 1)
@@ -280,6 +1198,13 @@ impl App {
     }
 
     pub fn on_down(&mut self) {
+        if self.prompt.is_some() {
+            return;
+        }
+        if let Some(editor) = self.editor_mut() {
+            return editor.down(1);
+        }
+
         match self.state {
             State::Day => {
                 if let Some(h) = self.day_highlight {
@@ -294,8 +1219,9 @@ impl App {
                 self.input_highlight = None;
             }
             State::Input => {
-                let day = self.days.get(self.day_highlight.unwrap()).unwrap();
-                let input_size = day.day.as_ref().map(|a| a.inputs().len()).unwrap_or(0);
+                let day = self.days().get(self.day_highlight.unwrap()).unwrap();
+                let part = self.part_highlight.unwrap() + 1;
+                let input_size = day.instances.iter().filter(|i| i.part == part).count();
                 if let Some(h) = self.input_highlight {
                     if h == input_size - 1 {
                         self.input_highlight = Some(0)
@@ -322,9 +1248,32 @@ impl App {
     }
 
     pub fn on_tick(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
+
+        if let Some(changes) = self.watcher.as_ref().map(Watcher::changes) {
+            self.on_changes(changes);
+        }
+
+        if let Some(result) = self.build.as_ref().and_then(|b| b.try_recv().ok()) {
+            self.on_build(result);
+        }
+
+        for day in self.years.iter_mut().flat_map(|y| y.days.iter_mut()) {
+            if let Some(notice) = day.update_checks() {
+                self.notice = Some(notice);
+            }
+        }
+
         let report = self.pool.update();
 
-        self.days.iter_mut().for_each(|day| {
+        let history = &self.history;
+        let days = self.years.iter_mut().flat_map(|y| {
+            let year = y.number;
+            y.days.iter_mut().map(move |d| (year, d))
+        });
+
+        days.for_each(|(year, day)| {
+            let number = day.number;
             day.instances.iter_mut().for_each(|i| {
                 i.update();
                 if let Some(id) = i.job_id {
@@ -337,9 +1286,32 @@ impl App {
                         .iter()
                         .find(|finished| finished.0 == id)
                     {
-                        i.status = match result.1 {
-                            Ok(d) => JobStatus::Finished(d),
-                            Err(_) => JobStatus::Error,
+                        if !i.temporary && !i.benching {
+                            let run = Run::new(year, number, i.part, i.variant, i.input, &result.1);
+                            if let Err(e) = history.record(run) {
+                                i.debug.push_str(&describe_error(&e));
+                            }
+                        }
+
+                        // The run may have fetched the input.
+                        i.view = None;
+                        i.status = match &result.1 {
+                            Ok(solution) => {
+                                i.answer = Some(solution.answer.clone());
+                                i.verdict = Some(solution.verdict.clone());
+                                match solution.verdict {
+                                    Verdict::Fail(_) => JobStatus::Wrong(solution.duration),
+                                    _ => JobStatus::Finished(solution.duration),
+                                }
+                            }
+                            Err(e) => match e.downcast_ref::<AocError>() {
+                                Some(AocError::Cancelled) => JobStatus::Cancelled,
+                                Some(AocError::TimedOut) => JobStatus::TimedOut,
+                                _ => {
+                                    i.debug.push_str(&describe_error(e));
+                                    JobStatus::Error
+                                }
+                            },
                         }
                     }
                 }
@@ -349,7 +1321,43 @@ impl App {
 
     fn run_input(&mut self) {
         if let Some(i) = self.day_highlight {
-            let day = self.days.get_mut(i).unwrap();
+            let day = self.years[self.year_highlight].days.get_mut(i).unwrap();
+
+            if let Some(i) = self.input_highlight {
+                let part = self.part_highlight.unwrap() + 1;
+                let instance = day
+                    .instances
+                    .iter_mut()
+                    .filter(|i| i.part == part)
+                    .nth(i)
+                    .unwrap();
+                instance.clear();
+                let day = day.day.as_ref().unwrap().clone();
+
+                let ctx = instance.communication.context();
+                let input = instance.input.to_string();
+                let variant = instance.variant;
+                let inputs = self.inputs.clone();
+
+                let (job_id, worker_id) = self.pool.register(
+                    move |cancel| {
+                        inputs.ensure(&input)?;
+                        day.run_timed(input, &ctx.with_cancel(cancel), part, variant)
+                    },
+                    self.options.timeout,
+                );
+
+                instance.job_id = Some(job_id);
+                instance.status = worker_id
+                    .map(JobStatus::Running)
+                    .unwrap_or(JobStatus::Waiting);
+            }
+        }
+    }
+
+    fn bench_input(&mut self) {
+        if let Some(i) = self.day_highlight {
+            let day = self.years[self.year_highlight].days.get_mut(i).unwrap();
 
             if let Some(i) = self.input_highlight {
                 let part = self.part_highlight.unwrap() + 1;
@@ -360,14 +1368,30 @@ impl App {
                     .nth(i)
                     .unwrap();
                 instance.clear();
+                instance.benching = true;
                 let day = day.day.as_ref().unwrap().clone();
 
-                let (output, debug) = instance.communication.senders();
+                let bench = instance.communication.bench_sender();
                 let input = instance.input.to_string();
+                let variant = instance.variant;
+                let inputs = self.inputs.clone();
 
-                let (job_id, worker_id) = self
-                    .pool
-                    .register(move || day.run_timed(input, output, debug, part));
+                let (job_id, worker_id) = self.pool.register(
+                    move |cancel| {
+                        inputs.ensure(&input)?;
+                        let report =
+                            day.bench(&input, part, variant, BenchConfig::default(), &cancel)?;
+                        let solution = Solution {
+                            verdict: Verdict::new(&report.answer, day.expected(&input, part)),
+                            answer: report.answer.clone(),
+                            parse: Duration::from_nanos(report.parse.median as u64),
+                            duration: Duration::from_nanos(report.solve.median as u64),
+                        };
+                        let _ = bench.send(report);
+                        Ok(solution)
+                    },
+                    self.options.timeout,
+                );
 
                 instance.job_id = Some(job_id);
                 instance.status = worker_id
@@ -377,31 +1401,460 @@ impl App {
         }
     }
 
+    fn cancel_input(&mut self) {
+        let job_id = self
+            .highlighted_instance()
+            .filter(|i| matches!(i.status, JobStatus::Waiting | JobStatus::Running(_)))
+            .and_then(|i| i.job_id);
+
+        if let Some(job_id) = job_id {
+            self.pool.cancel(job_id);
+        }
+    }
+
+    /// Submits the answer of the highlighted instance, away from the UI thread
+    /// as it goes over the network.
+    fn submit_input(&mut self) {
+        let (day, part, input) = match (
+            self.day_highlight,
+            self.part_highlight,
+            self.input_highlight,
+        ) {
+            (Some(d), Some(p), Some(i)) => (d, p + 1, i),
+            _ => return,
+        };
+
+        let year = self.year();
+        let day = self.years[self.year_highlight].days.get_mut(day).unwrap();
+        let number = day.number;
+        let instance = day
+            .instances
+            .iter_mut()
+            .filter(|i| i.part == part)
+            .nth(input)
+            .unwrap();
+
+        // Answers to the examples would only cost a wrong answer, and a wait.
+        if Path::new(instance.input) != self.inputs.cached_path(year, number) {
+            self.notice = Some(String::from("Only answers to the real input are submitted"));
+            return;
+        }
+
+        if let (Some(answer), false) = (instance.answer.clone(), instance.submitting) {
+            instance.submitting = true;
+            instance.receipt = None;
+
+            let receipt = instance.communication.receipt_sender();
+            let submitter = self.submitter.clone();
+
+            thread::spawn(move || {
+                let _ = receipt.send(submitter.submit(year, number, part, &answer));
+            });
+        }
+    }
+
+    /// Queues every instance of a day, that is each variant of both parts on
+    /// every input.
+    fn run_day(&mut self, day: usize) {
+        self.run_instances(self.year_highlight, day, |_| true);
+    }
+
+    /// Queues the instances of a day picked by `filter`, cancelling the runs
+    /// of theirs that haven't finished.
+    fn run_instances(&mut self, year: usize, day: usize, filter: impl Fn(&Instance) -> bool) {
+        let d = &mut self.years[year].days[day];
+
+        // The runs supersede what a cross-check found on their inputs.
+        let rerun = d
+            .instances
+            .iter()
+            .filter(|i| filter(i))
+            .map(|i| (i.input, i.part))
+            .collect::<Vec<_>>();
+        d.checks
+            .retain(|c| !rerun.contains(&(c.input.as_str(), c.part)));
+
+        if let Some(a) = d.day.as_ref() {
+            d.instances.iter_mut().filter(|i| filter(i)).for_each(|i| {
+                if let (Some(job_id), JobStatus::Waiting | JobStatus::Running(_)) =
+                    (i.job_id, i.status)
+                {
+                    self.pool.cancel(job_id);
+                }
+
+                let ctx = i.communication.context();
+                let input = i.input.to_string();
+                let part = i.part;
+                let variant = i.variant;
+                i.clear();
+
+                let a = a.clone();
+                let inputs = self.inputs.clone();
+
+                let (job_id, worker_id) = self.pool.register(
+                    move |cancel| {
+                        inputs.ensure(&input)?;
+                        a.run_timed(input, &ctx.with_cancel(cancel), part, variant)
+                    },
+                    self.options.timeout,
+                );
+
+                i.job_id = Some(job_id);
+                i.status = worker_id
+                    .map(JobStatus::Running)
+                    .unwrap_or(JobStatus::Waiting);
+            })
+        }
+    }
+
     fn run_all(&mut self) {
-        self.days.iter_mut().for_each(|d| {
-            if let Some(a) = d.day.as_ref() {
-                d.instances.iter_mut().for_each(|i| {
-                    let (output, debug) = i.communication.senders();
-                    let input = i.input.to_string();
-                    let part = i.part;
-                    i.clear();
-
-                    let a = a.clone();
-
-                    let (job_id, worker_id) = self
-                        .pool
-                        .register(move || a.run_timed(input, output, debug, part));
-
-                    i.job_id = Some(job_id);
-                    i.status = worker_id
-                        .map(JobStatus::Running)
-                        .unwrap_or(JobStatus::Waiting);
-                })
+        for day in 0..self.days().len() {
+            self.run_day(day);
+        }
+    }
+
+    /// Runs all the variants of the highlighted day on each of its inputs,
+    /// parsing them once, so that the ones that disagree get flagged. Inputs
+    /// that can't be fetched are skipped, as by `aoc check`.
+    fn cross_check(&mut self) {
+        let d = match self.day_highlight {
+            Some(d) => &mut self.years[self.year_highlight].days[d],
+            None => return,
+        };
+        let day = match &d.day {
+            Some(day) => day.clone(),
+            None => return,
+        };
+
+        let inputs = d
+            .instances
+            .iter()
+            .filter(|i| !i.temporary)
+            .map(|i| i.input)
+            .unique()
+            .collect::<Vec<_>>();
+        let manager = self.inputs.clone();
+
+        let (tx, rx) = mpsc::channel();
+        d.checks.clear();
+        d.cross_checks = Some(rx);
+        self.notice = Some(format!(
+            "Cross-checking the variants of day {}...",
+            d.number
+        ));
+
+        thread::spawn(move || {
+            for input in inputs {
+                if manager.ensure(input).is_err() || !Path::new(input).exists() {
+                    continue;
+                }
+
+                for part in 1..=2 {
+                    let check = crosscheck::cross_check(day.as_ref().as_ref(), input, part)
+                        .unwrap_or_else(|e| CrossCheck {
+                            input: input.to_string(),
+                            part,
+                            results: day
+                                .variants(part)
+                                .into_iter()
+                                .map(|v| (v, Err(format!("{:#}", e))))
+                                .collect(),
+                        });
+
+                    if tx.send(check).is_err() {
+                        return;
+                    }
+                }
             }
         });
     }
 
+    pub fn on_backspace(&mut self) {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.pop();
+            self.update_search();
+        } else if let Some(editor) = self.editor_mut() {
+            editor.backspace();
+        }
+    }
+
+    /// Dismisses the prompt, and the search it was typing if any, or else
+    /// brings back the other panes.
+    pub fn on_escape(&mut self) {
+        match self.prompt.take() {
+            Some(Prompt {
+                action: PromptAction::Search(kind),
+                ..
+            }) => self.pane_mut(kind).search = None,
+            Some(_) => {}
+            None if self.editing => self.editing = false,
+            None if self.maximised => self.maximised = false,
+            None => {
+                self.build_log = None;
+                if self.focus == PaneKind::Build {
+                    self.focus = PaneKind::Output;
+                }
+            }
+        }
+    }
+
+    pub fn pane(&self, kind: PaneKind) -> &Pane {
+        match kind {
+            PaneKind::Output => &self.output_pane,
+            PaneKind::Debug => &self.debug_pane,
+            PaneKind::Input => &self.input_pane,
+            PaneKind::Build => &self.build_pane,
+        }
+    }
+
+    pub(crate) fn pane_mut(&mut self, kind: PaneKind) -> &mut Pane {
+        match kind {
+            PaneKind::Output => &mut self.output_pane,
+            PaneKind::Debug => &mut self.debug_pane,
+            PaneKind::Input => &mut self.input_pane,
+            PaneKind::Build => &mut self.build_pane,
+        }
+    }
+
+    /// The text of a pane, from the highlighted instance.
+    pub fn pane_text(&self, kind: PaneKind) -> &str {
+        match (self.highlighted_instance(), kind) {
+            (_, PaneKind::Build) => self.build_log.as_deref().unwrap_or_default(),
+            (Some(i), PaneKind::Output) => &i.output,
+            (Some(i), PaneKind::Debug) => &i.debug,
+            (Some(i), PaneKind::Input) => match &i.view {
+                Some(Ok(view)) => &view.text,
+                _ => "",
+            },
+            (None, _) => "",
+        }
+    }
+
+    /// Width of the longest line of the input in the viewer.
+    fn input_width(&self) -> usize {
+        match self.highlighted_instance().and_then(|i| i.view.as_ref()) {
+            Some(Ok(view)) => view.width,
+            _ => 0,
+        }
+    }
+
+    /// Reads the input of the highlighted instance for the viewer, unless it
+    /// was read already.
+    pub(crate) fn load_input_view(&mut self) {
+        if let Some(i) = self.highlighted_instance_mut() {
+            if i.view.is_none() {
+                i.view = Some(InputView::load(Path::new(i.input)).map_err(|e| format!("{:#}", e)));
+            }
+        }
+    }
+
+    /// Applies `scroll` to the focused pane, unless a prompt is open.
+    fn scroll(&mut self, scroll: impl FnOnce(&mut Pane, usize)) {
+        if self.prompt.is_some() {
+            return;
+        }
+
+        let lines = self.pane_text(self.focus).split_terminator('\n').count();
+        scroll(self.pane_mut(self.focus), lines);
+    }
+
+    pub fn on_page_up(&mut self) {
+        if let Some(editor) = self.editor_mut() {
+            return editor.up(PAGE);
+        }
+        self.scroll(Pane::page_up);
+    }
+
+    pub fn on_page_down(&mut self) {
+        if let Some(editor) = self.editor_mut() {
+            return editor.down(PAGE);
+        }
+        self.scroll(Pane::page_down);
+    }
+
+    pub fn on_home(&mut self) {
+        if let Some(editor) = self.editor_mut() {
+            return editor.home();
+        }
+        self.scroll(|p, _| p.home());
+    }
+
+    pub fn on_end(&mut self) {
+        if let Some(editor) = self.editor_mut() {
+            return editor.end();
+        }
+        self.scroll(|p, _| p.end());
+    }
+
+    /// F1 and F2 run a part on the text of the editor.
+    pub fn on_function(&mut self, n: u8) {
+        if self.prompt.is_none() && self.editing && (1..=2).contains(&n) {
+            self.run_adhoc(n as usize);
+        }
+    }
+
+    /// Ctrl-S saves the text of the editor as an input file.
+    pub fn on_ctrl(&mut self, c: char) {
+        if c != 's' || self.prompt.is_some() || !self.editing {
+            return;
+        }
+
+        let path = self
+            .days()
+            .get(self.day_highlight.unwrap())
+            .and_then(|d| d.day.as_ref()?.inputs().first().copied())
+            .and_then(|i| Some(Path::new(i).parent()?.join("example.txt")))
+            .unwrap_or_else(|| {
+                self.inputs
+                    .day_dir(self.year(), self.day_highlight.unwrap() + 1)
+                    .join("example.txt")
+            });
+
+        self.prompt = Some(Prompt::new(
+            "Save the input as",
+            path.to_string_lossy(),
+            PromptAction::SaveInput,
+        ));
+    }
+
+    /// The editor of the highlighted day, while it has the keys.
+    pub(crate) fn editor_mut(&mut self) -> Option<&mut Editor> {
+        match self.day_highlight {
+            Some(d) if self.editing => Some(&mut self.years[self.year_highlight].days[d].editor),
+            _ => None,
+        }
+    }
+
+    /// Runs a part on the text of the editor, as a temporary instance of the
+    /// highlighted day, and selects it.
+    fn run_adhoc(&mut self, part: usize) {
+        let day = match self.day_highlight {
+            Some(d) if self.is_day_present(d) => d,
+            _ => return,
+        };
+
+        let year = self.year();
+        let d = &mut self.years[self.year_highlight].days[day];
+        if d.editor.is_empty() {
+            self.notice = Some(String::from("Type or paste an input to run first"));
+            return;
+        }
+
+        let path = self
+            .options
+            .fetch
+            .data_dir
+            .join(ADHOC)
+            .join(year.to_string())
+            .join(format!("day{}.txt", d.number));
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, d.editor.text()));
+        if let Err(e) = written {
+            self.notice = Some(format!("Could not write {}: {}", path.display(), e));
+            return;
+        }
+
+        // Instances hold on to their input for as long as the TUI runs.
+        let input = *d
+            .adhoc
+            .get_or_insert_with(|| Box::leak(path.to_string_lossy().into_owned().into_boxed_str()));
+        if !d.instances.iter().any(|i| i.temporary && i.part == part) {
+            let mut instance = Instance::new(input, part, MAIN_VARIANT);
+            instance.temporary = true;
+            d.instances.push(instance);
+        }
+
+        self.run_instances(self.year_highlight, day, |i| i.temporary && i.part == part);
+
+        self.state = State::Input;
+        self.part_highlight = Some(part - 1);
+        self.input_highlight = self.days()[day]
+            .instances
+            .iter()
+            .filter(|i| i.part == part)
+            .position(|i| i.temporary);
+    }
+
+    /// Writes the text of the editor to a new input file.
+    fn save_input(&mut self, path: &str) {
+        let text = match self.day_highlight {
+            Some(d) => self.days()[d].editor.text(),
+            None => return,
+        };
+
+        let path = Path::new(path);
+        let saved = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| OpenOptions::new().write(true).create_new(true).open(path))
+            .and_then(|mut file| file.write_all(text.as_bytes()));
+
+        self.notice = Some(match saved {
+            Ok(()) => match path.file_stem() {
+                Some(name) => format!(
+                    "Saved {}; add \"{}\" to the inputs of the day to keep it",
+                    path.display(),
+                    name.to_string_lossy()
+                ),
+                None => format!("Saved {}", path.display()),
+            },
+            Err(e) => format!("Could not save {}: {}", path.display(), e),
+        });
+    }
+
+    /// Takes the search being typed, and brings its first match in view.
+    fn update_search(&mut self) {
+        if let Some(Prompt {
+            action: PromptAction::Search(kind),
+            text,
+            ..
+        }) = &self.prompt
+        {
+            let (kind, query) = (*kind, text.clone());
+            let text = self.pane_text(kind).to_string();
+            let pane = self.pane_mut(kind);
+
+            pane.search = Some(query);
+            pane.find(&text, true, true);
+        }
+    }
+
+    /// Brings the next or previous match of the focused pane in view.
+    fn find(&mut self, forward: bool) {
+        let text = self.pane_text(self.focus).to_string();
+        self.pane_mut(self.focus).find(&text, forward, false);
+    }
+
+    /// Writes the Markdown and JSON reports of the year being shown.
+    fn export(&mut self, path: &str) {
+        let report = self
+            .history
+            .latest()
+            .map(|runs| Report::new(self.year(), &runs))
+            .and_then(|r| r.write(Path::new(path)));
+
+        self.notice = Some(match report {
+            Ok((markdown, json)) => format!("Wrote {} and {}", markdown.display(), json.display()),
+            Err(e) => format!("Export failed: {:#}", e),
+        });
+    }
+
     pub fn on_enter(&mut self) {
+        if let Some(prompt) = self.prompt.take() {
+            match prompt.action {
+                PromptAction::Export => self.export(&prompt.text),
+                PromptAction::Search(_) => {}
+                PromptAction::SaveInput => self.save_input(&prompt.text),
+            }
+            return;
+        }
+
+        if let Some(editor) = self.editor_mut() {
+            return editor.insert('\n');
+        }
+
         match self.state {
             State::Day => self.part_selection(),
             State::Part => self.input_selection(),
@@ -409,22 +1862,52 @@ impl App {
         }
     }
 
+    pub fn highlighted_instance(&self) -> Option<&Instance> {
+        let day = self.days().get(self.day_highlight?)?;
+        let part = self.part_highlight? + 1;
+
+        day.instances
+            .iter()
+            .filter(|i| i.part == part)
+            .nth(self.input_highlight?)
+    }
+
+    fn highlighted_instance_mut(&mut self) -> Option<&mut Instance> {
+        let (day, part, input) = (
+            self.day_highlight?,
+            self.part_highlight? + 1,
+            self.input_highlight?,
+        );
+
+        self.years[self.year_highlight]
+            .days
+            .get_mut(day)?
+            .instances
+            .iter_mut()
+            .filter(|i| i.part == part)
+            .nth(input)
+    }
+
     pub fn is_day_present(&self, idx: usize) -> bool {
-        self.days.get(idx).unwrap().day.is_some()
+        self.days().get(idx).unwrap().day.is_some()
     }
 
+    #[allow(dead_code)]
     pub fn selecting_day(&self) -> bool {
         matches!(self.state, State::Day)
     }
 
+    #[allow(dead_code)]
     pub fn selecting_part(&self) -> bool {
         matches!(self.state, State::Part)
     }
 
+    #[allow(dead_code)]
     pub fn selecting_input(&self) -> bool {
         matches!(self.state, State::Input)
     }
 
+    #[allow(dead_code)]
     pub fn state(&self) -> State {
         self.state
     }
@@ -432,6 +1915,171 @@ impl App {
 
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(Options::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::{config, temp_dir, TempDir};
+
+    /// An app keeping its records in a directory of its own.
+    fn app() -> (TempDir, App) {
+        let dir = temp_dir("app");
+        let app = App::new(Options {
+            fetch: config(&dir, None),
+            ..Options::default()
+        });
+        (dir, app)
+    }
+
+    #[test]
+    fn cross_checks_flag_disagreeing_variants() {
+        let (_dir, mut app) = app();
+        let day = &mut app.years[app.year_highlight].days[0];
+        let input = day.instances[0].input;
+
+        let (tx, rx) = mpsc::channel();
+        day.cross_checks = Some(rx);
+        tx.send(CrossCheck {
+            input: input.to_string(),
+            part: 1,
+            results: vec![
+                (MAIN_VARIANT, Ok(Answer::Integer(7))),
+                ("slow", Err(String::from("overflow"))),
+            ],
+        })
+        .unwrap();
+        tx.send(CrossCheck {
+            input: input.to_string(),
+            part: 2,
+            results: vec![(MAIN_VARIANT, Ok(Answer::Integer(5)))],
+        })
+        .unwrap();
+        drop(tx);
+
+        app.on_tick();
+
+        let day = &app.days()[0];
+        let part = |p| day.instances.iter().find(|i| i.part == p).unwrap();
+        assert!(day.mismatch(part(1)));
+        assert!(!day.mismatch(part(2)));
+        assert!(day.disagrees(1));
+        assert_eq!(day.answer_summary(part(1)), "7");
+        assert_eq!(
+            app.notice.as_deref(),
+            Some("Day 1: the variants disagree on 1 of 2 parts and inputs")
+        );
+    }
+
+    #[test]
+    fn variants_answer_for_the_input_they_last_ran_on() {
+        let (_dir, mut app) = app();
+        let day = &mut app.years[app.year_highlight].days[0];
+        let inputs = day.instances.iter().map(|i| i.input).unique().collect_vec();
+        let (small, real) = (inputs[0], inputs[inputs.len() - 1]);
+
+        assert!(day.answering(1, MAIN_VARIANT, None).is_none());
+
+        for (input, answer) in [(small, 7), (real, 1400)] {
+            let i = day
+                .instances
+                .iter_mut()
+                .find(|i| i.part == 1 && i.variant == MAIN_VARIANT && i.input == input)
+                .unwrap();
+            i.answer = Some(Answer::Integer(answer));
+        }
+
+        let answer = |input| {
+            day.answering(1, MAIN_VARIANT, input)
+                .and_then(|i| i.answer.clone())
+        };
+        assert_eq!(answer(None), Some(Answer::Integer(1400)));
+        assert_eq!(answer(Some(small)), Some(Answer::Integer(7)));
+    }
+
+    #[test]
+    fn resume_states_round_trip() {
+        let resumes = [
+            Resume {
+                year: 2021,
+                day: Some(2),
+                part: Some(1),
+                input: Some(0),
+            },
+            Resume {
+                year: 2021,
+                day: Some(0),
+                part: None,
+                input: None,
+            },
+        ];
+
+        assert_eq!(resumes[0].to_string(), "2021:2:1:0");
+        assert_eq!(resumes[1].to_string(), "2021:0:-:-");
+        for resume in resumes {
+            assert_eq!(resume.to_string().parse::<Resume>().unwrap(), resume);
+        }
+
+        assert!("2021:1:1".parse::<Resume>().is_err());
+        assert!("2021:x:-:-".parse::<Resume>().is_err());
+        assert!("-:1:1:1".parse::<Resume>().is_err());
+    }
+
+    #[test]
+    fn restarts_resume_the_selection() {
+        let resume = Resume {
+            year: 2021,
+            day: Some(1),
+            part: Some(1),
+            input: Some(0),
+        };
+
+        let dir = temp_dir("app");
+        let mut app = App::new(Options {
+            fetch: config(&dir, None),
+            resume: Some(resume.to_string().parse().unwrap()),
+            ..Options::default()
+        });
+        assert_eq!(app.day_highlight, Some(1));
+        assert_eq!(app.part_highlight, Some(1));
+        assert_eq!(app.input_highlight, Some(0));
+        assert!(matches!(app.state, State::Input));
+
+        app.on_changes(vec![Change::Binary]);
+        assert_eq!(app.restart(), Some(resume));
+    }
+
+    #[test]
+    fn failed_builds_show_in_the_build_pane() {
+        let (_dir, mut app) = app();
+        let output = "error[E0425]: cannot find value `x` in this scope";
+
+        app.on_build(Err(BuildError(output.to_string()).into()));
+
+        assert_eq!(app.build_log.as_deref(), Some(output));
+        assert_eq!(app.focus, PaneKind::Build);
+        assert_eq!(app.notice.as_deref(), Some("Failed to build the solutions"));
+        assert!(app.build.is_none());
+    }
+
+    #[test]
+    fn failed_loads_show_in_the_build_pane() {
+        let (_dir, mut app) = app();
+        let dir = temp_dir("reload");
+        let library = dir.join("libaoc.so");
+        fs::write(&library, "not a library").unwrap();
+
+        app.on_build(Ok(library));
+
+        assert!(app.build_log.as_ref().unwrap().contains("Failed to load"));
+        assert_eq!(app.focus, PaneKind::Build);
+        assert!(!hot::is_loaded());
+
+        // A later failure replaces the log of the previous one.
+        app.on_build(Ok(dir.join("missing.so")));
+        assert!(app.build_log.as_ref().unwrap().contains("Failed to copy"));
     }
 }
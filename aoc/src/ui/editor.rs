@@ -0,0 +1,222 @@
+/// Text typed or pasted in the TUI, to run a day on, with a cursor.
+#[derive(Debug, Clone)]
+pub struct Editor {
+    pub(crate) lines: Vec<String>,
+    /// Line of the cursor.
+    pub(crate) row: usize,
+    /// Position of the cursor in its line, in characters.
+    pub(crate) column: usize,
+    /// First line in view, kept so that the cursor is.
+    pub(crate) offset: usize,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            column: 0,
+            offset: 0,
+        }
+    }
+}
+
+impl Editor {
+    /// The text, with a line ending after each line as input files have.
+    pub fn text(&self) -> String {
+        let mut lines = self.lines.as_slice();
+        // Pasting a whole input leaves an empty line after the last one.
+        if let [rest @ .., last] = lines {
+            if last.is_empty() {
+                lines = rest;
+            }
+        }
+
+        lines.iter().map(|l| format!("{}\n", l)).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(String::is_empty)
+    }
+
+    /// Byte index of the cursor in its line.
+    fn index(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.column)
+            .map_or(line.len(), |(i, _)| i)
+    }
+
+    fn width(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    pub fn insert(&mut self, c: char) {
+        if c == '\n' {
+            return self.newline();
+        }
+
+        let i = self.index();
+        self.lines[self.row].insert(i, c);
+        self.column += 1;
+    }
+
+    fn newline(&mut self) {
+        let i = self.index();
+        let rest = self.lines[self.row].split_off(i);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.column = 0;
+    }
+
+    /// Deletes the character before the cursor, joining lines at the start of
+    /// one.
+    pub fn backspace(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+            let i = self.index();
+            self.lines[self.row].remove(i);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.column = self.width(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.column = self.width(self.row);
+        }
+    }
+
+    pub fn right(&mut self) {
+        if self.column < self.width(self.row) {
+            self.column += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.column = 0;
+        }
+    }
+
+    pub fn up(&mut self, n: usize) {
+        self.row = self.row.saturating_sub(n);
+        self.column = self.column.min(self.width(self.row));
+    }
+
+    pub fn down(&mut self, n: usize) {
+        self.row = (self.row + n).min(self.lines.len() - 1);
+        self.column = self.column.min(self.width(self.row));
+    }
+
+    pub fn home(&mut self) {
+        self.column = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.column = self.width(self.row);
+    }
+
+    /// Scrolls just enough for the cursor to be in view, in `height` lines.
+    pub fn scroll(&mut self, height: usize) {
+        if self.row < self.offset {
+            self.offset = self.row;
+        } else if height > 0 && self.row >= self.offset + height {
+            self.offset = self.row + 1 - height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> Editor {
+        let mut editor = Editor::default();
+        text.chars().for_each(|c| editor.insert(c));
+        editor
+    }
+
+    #[test]
+    fn typed_text_ends_its_lines() {
+        assert!(Editor::default().is_empty());
+        assert_eq!(Editor::default().text(), "");
+
+        let editor = typed("199\n200");
+        assert!(!editor.is_empty());
+        assert_eq!(editor.text(), "199\n200\n");
+        assert_eq!((editor.row, editor.column), (1, 3));
+
+        // As when pasting a whole input.
+        assert_eq!(typed("199\n200\n").text(), "199\n200\n");
+    }
+
+    #[test]
+    fn inserting_in_the_middle_of_a_line() {
+        let mut editor = typed("a€c");
+        editor.left();
+        editor.left();
+        editor.insert('b');
+        assert_eq!(editor.text(), "ab€c\n");
+
+        editor.insert('\n');
+        assert_eq!(editor.text(), "ab\n€c\n");
+        assert_eq!((editor.row, editor.column), (1, 0));
+    }
+
+    #[test]
+    fn backspace_joins_lines() {
+        let mut editor = typed("ab\ncd");
+        editor.home();
+        editor.backspace();
+        assert_eq!(editor.text(), "abcd\n");
+        assert_eq!((editor.row, editor.column), (0, 2));
+
+        editor.backspace();
+        assert_eq!(editor.text(), "acd\n");
+
+        editor.home();
+        editor.backspace();
+        assert_eq!(editor.text(), "acd\n");
+    }
+
+    #[test]
+    fn the_cursor_stays_in_the_text() {
+        let mut editor = typed("long line\nab\n");
+        assert_eq!((editor.row, editor.column), (2, 0));
+
+        editor.left();
+        assert_eq!((editor.row, editor.column), (1, 2));
+        editor.right();
+        assert_eq!((editor.row, editor.column), (2, 0));
+        editor.right();
+        assert_eq!((editor.row, editor.column), (2, 0));
+
+        editor.up(10);
+        editor.end();
+        assert_eq!((editor.row, editor.column), (0, 9));
+        editor.down(1);
+        assert_eq!((editor.row, editor.column), (1, 2));
+        editor.down(10);
+        assert_eq!((editor.row, editor.column), (2, 0));
+    }
+
+    #[test]
+    fn scrolling_keeps_the_cursor_in_view() {
+        let mut editor = typed(&"x\n".repeat(20));
+
+        editor.scroll(5);
+        assert_eq!(editor.offset, 16);
+
+        editor.up(3);
+        editor.scroll(5);
+        assert_eq!(editor.offset, 16);
+
+        editor.up(10);
+        editor.scroll(5);
+        assert_eq!(editor.offset, 7);
+    }
+}
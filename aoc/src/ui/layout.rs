@@ -1,23 +1,84 @@
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-use crate::ui::app::{App, State};
+use crate::bench::{format_nanos, BenchReport};
+use crate::fetch::InputStatus;
+use crate::hot;
+use crate::submit::{Receipt, SubmitOutcome};
+use crate::ui::app::{App, Day, JobStatus, State, SPINNER};
+use crate::ui::{Pane, PaneKind, Prompt};
+use crate::{format_duration, MAIN_VARIANT};
 
 use tui::backend::Backend;
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
-use tui::text::Spans;
-use tui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Tabs};
 use tui::Frame;
 
+/// The total solve time of a variant, in red if it got slower than the
+/// baseline.
+fn time_cell(app: &App, day: &Day, part: Option<usize>, variant: &str) -> Cell<'static> {
+    let text = day
+        .time(part, variant)
+        .map(|d| format_duration(&d))
+        .unwrap_or_default();
+
+    if app.slower(day, part, variant) {
+        Cell::from(text).style(Style::default().fg(Color::Red))
+    } else {
+        Cell::from(text)
+    }
+}
+
+/// The answer of a variant on the input highlighted, or else on the last
+/// input it answered, in red if the variants disagree on it.
+fn answer_cell(app: &App, day: &Day, part: usize, variant: &str) -> Cell<'static> {
+    let input = app
+        .input_highlight
+        .filter(|_| app.part_highlight == Some(part - 1))
+        .and_then(|n| day.instances.iter().filter(|i| i.part == part).nth(n))
+        .map(|i| i.input);
+
+    match day.answering(part, variant, input) {
+        Some(i) if day.mismatch(i) => {
+            Cell::from(day.answer_summary(i)).style(Style::default().fg(Color::Red))
+        }
+        Some(i) => Cell::from(day.answer_summary(i)),
+        None => Cell::from(String::new()),
+    }
+}
+
+/// The variants of a part, listed under it when there are several.
+fn variant_rows(app: &App, day: &Day, part: usize) -> Vec<Row<'static>> {
+    let variants = day.variants(part);
+    if variants.len() < 2 {
+        return Vec::new();
+    }
+
+    variants
+        .into_iter()
+        .map(|v| {
+            let status = day.status_for_variant(part, v);
+            Row::new([
+                Cell::from(String::new()),
+                Cell::from(format!("  └ {}", v)),
+                time_cell(app, day, Some(part), v),
+                answer_cell(app, day, part, v),
+                Cell::from(status.to_string()).style(status.style()),
+            ])
+            .bottom_margin(0)
+            .style(Style::default().fg(Color::Gray))
+        })
+        .collect()
+}
+
 pub fn draw_list<B>(f: &mut Frame<B>, rect: Rect, app: &mut App)
 where
     B: Backend,
 {
     let selected_style = match app.day_highlight {
         Some(n) => {
-            if app.days.get(n).unwrap().day.is_some() {
+            if app.days().get(n).unwrap().day.is_some() {
                 Style::default().bg(Color::DarkGray)
             } else {
                 Style::default()
@@ -26,28 +87,26 @@ where
         None => Style::default(),
     };
 
-    let header_cells = ["Day", "Title", "Status"]
+    let header_cells = ["Day", "Title", "Time", "Answer", "Status"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
 
     let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-    let rows = app.days.iter().flat_map(|d| {
+    let rows = app.days().iter().flat_map(|d| {
         let number = Cell::from(format!("{}", d.number));
-        let title = Cell::from(
-            d.day
-                .as_ref()
-                .map(|d| d.title())
-                .unwrap_or_else(String::new),
-        );
+        let title = Cell::from(d.day.as_ref().map(|d| d.title()).unwrap_or_default());
 
-        let status = if d.day.is_some() {
-            Cell::from(format!("{}", d.status())).style(d.status().style())
+        let (time, status) = if d.day.is_some() {
+            (
+                time_cell(app, d, None, MAIN_VARIANT),
+                Cell::from(d.status_text(None, app.tick)).style(d.status().style()),
+            )
         } else {
-            Cell::from(String::new())
+            (Cell::from(String::new()), Cell::from(String::new()))
         };
 
-        let row = Row::new([number, title, status])
+        let row = Row::new([number, title, time, Cell::from(String::new()), status])
             .bottom_margin(0)
             .style(if d.day.is_some() {
                 Style::default()
@@ -57,33 +116,33 @@ where
                     .add_modifier(Modifier::ITALIC)
             });
 
-        if app.part_highlight.is_some() {
-            if d.number == app.day_highlight.unwrap() + 1 {
-                let status =
-                    Cell::from(format!("{}", d.status_for_part(1))).style(d.status().style());
-
-                let part_1 = Row::new([
-                    Cell::from(String::new()),
-                    Cell::from(String::from("Part 1")),
-                    status,
-                ])
-                .bottom_margin(0)
-                .style(Style::default().add_modifier(Modifier::ITALIC));
-
-                let status =
-                    Cell::from(format!("{}", d.status_for_part(2))).style(d.status().style());
-
-                let part_2 = Row::new([
-                    Cell::from(String::new()),
-                    Cell::from(String::from("Part 2")),
-                    status,
-                ])
-                .bottom_margin(0)
-                .style(Style::default().add_modifier(Modifier::ITALIC));
-                vec![row, part_1, part_2]
-            } else {
-                vec![row]
+        if app.part_highlight.is_some() && d.number == app.day_highlight.unwrap() + 1 {
+            let mut rows = vec![row];
+
+            for part in 1..=2 {
+                let status = if d.disagrees(part) {
+                    Cell::from("Disagree").style(Style::default().fg(Color::Red))
+                } else {
+                    Cell::from(d.status_text(Some(part), app.tick))
+                        .style(d.status_for_part(part).style())
+                };
+
+                rows.push(
+                    Row::new([
+                        Cell::from(String::new()),
+                        Cell::from(format!("Part {}", part)),
+                        time_cell(app, d, Some(part), MAIN_VARIANT),
+                        answer_cell(app, d, part, MAIN_VARIANT),
+                        status,
+                    ])
+                    .bottom_margin(0)
+                    .style(Style::default().add_modifier(Modifier::ITALIC)),
+                );
+
+                rows.extend(variant_rows(app, d, part));
             }
+
+            rows
         } else {
             vec![row]
         }
@@ -91,23 +150,32 @@ where
 
     let table = Table::new(rows)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("AoC 2021"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("AoC {}", app.year())),
+        )
         .highlight_style(selected_style)
         .highlight_symbol("> ")
         .widths(&[
             Constraint::Min(4),
-            Constraint::Percentage(50),
-            Constraint::Percentage(50),
+            Constraint::Percentage(30),
+            Constraint::Length(9),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
         ]);
 
     let mut state = TableState::default();
     if let State::Day = app.state {
         state.select(app.day_highlight);
     } else {
-        state.select(
-            app.day_highlight
-                .map(|i| i + app.part_highlight.unwrap() + 1),
-        )
+        state.select(app.day_highlight.map(|i| {
+            let day = &app.days()[i];
+            match app.part_highlight.unwrap() {
+                0 => i + 1,
+                _ => i + 2 + variant_rows(app, day, 1).len(),
+            }
+        }))
     }
     f.render_stateful_widget(table, rect, &mut state);
 }
@@ -116,7 +184,7 @@ pub fn draw_inputs<B>(f: &mut Frame<B>, rect: Rect, app: &mut App)
 where
     B: Backend,
 {
-    let header_cells = ["Input file", "Time", "Status"]
+    let header_cells = ["Input file", "Time", "Answer", "Status"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
 
@@ -124,7 +192,7 @@ where
 
     let rows = if let Some(n) = app.day_highlight {
         if let Some(part) = app.part_highlight {
-            let day = app.days.get(n).unwrap();
+            let day = app.days().get(n).unwrap();
             let part = part + 1;
 
             if day.day.is_some() {
@@ -132,17 +200,39 @@ where
                     .iter()
                     .filter(|i| i.part == part)
                     .map(|r| {
-                        let i = Path::new(r.input)
+                        let file = Path::new(r.input)
                             .file_name()
                             .and_then(|s| s.to_str())
-                            .unwrap_or("")
-                            .to_string();
-
-                        let t = r.duration().unwrap_or_else(String::new);
-
-                        let s = Cell::from(format!("{}", r.status)).style(r.status.style());
-
-                        Row::new([Cell::from(i), Cell::from(t), s]).bottom_margin(0)
+                            .unwrap_or("");
+                        let i = if r.temporary {
+                            String::from("(ad hoc)")
+                        } else if r.variant == MAIN_VARIANT {
+                            file.to_string()
+                        } else {
+                            format!("{} ({})", file, r.variant)
+                        };
+
+                        let t = Cell::from(r.duration().unwrap_or_default());
+                        let t = if app.regressed(day, r) {
+                            t.style(Style::default().fg(Color::Red))
+                        } else {
+                            t
+                        };
+
+                        let a = day.answer_summary(r);
+
+                        let fetchable = matches!(r.status, JobStatus::Ready)
+                            && app.inputs.status(r.input) == InputStatus::Fetchable;
+
+                        let s = if fetchable {
+                            Cell::from("Fetchable").style(Style::default().fg(Color::Cyan))
+                        } else if day.mismatch(r) {
+                            Cell::from("Mismatch").style(Style::default().fg(Color::Red))
+                        } else {
+                            Cell::from(r.status_text(app.tick)).style(r.status.style())
+                        };
+
+                        Row::new([Cell::from(i), t, Cell::from(a), s]).bottom_margin(0)
                     })
                     .collect()
             } else {
@@ -161,9 +251,10 @@ where
         .highlight_style(Style::default().bg(Color::DarkGray))
         .highlight_symbol("> ")
         .widths(&[
+            Constraint::Percentage(25),
+            Constraint::Percentage(20),
             Constraint::Percentage(30),
-            Constraint::Percentage(40),
-            Constraint::Percentage(30),
+            Constraint::Percentage(25),
         ]);
 
     let mut state = TableState::default();
@@ -172,108 +263,432 @@ where
     f.render_stateful_widget(table, rect, &mut state);
 }
 
-pub fn draw_input_preview<B>(f: &mut Frame<B>, rect: Rect, app: &mut App)
+pub fn draw_bench<B>(f: &mut Frame<B>, rect: Rect, report: &BenchReport)
+where
+    B: Backend,
+{
+    let header_cells = ["", "Min", "Median", "Mean", "p95", "Std dev", "Runs"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+
+    let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+    let rows = [
+        ("Load", &report.load),
+        ("Parse", &report.parse),
+        ("Solve", &report.solve),
+    ]
+    .into_iter()
+    .map(|(name, stats)| {
+        Row::new([
+            Cell::from(name),
+            Cell::from(format_nanos(stats.min)),
+            Cell::from(format_nanos(stats.median)),
+            Cell::from(format_nanos(stats.mean)),
+            Cell::from(format_nanos(stats.p95)),
+            Cell::from(format_nanos(stats.std_dev)),
+            Cell::from(format!("{}", stats.samples)),
+        ])
+        .bottom_margin(0)
+    });
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title("Benchmark"))
+        .widths(&[
+            Constraint::Length(5),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(10),
+        ]);
+
+    f.render_widget(table, rect);
+}
+
+fn receipt_style(receipt: &Receipt) -> Style {
+    match receipt.outcome {
+        SubmitOutcome::Correct => Style::default().fg(Color::Green),
+        SubmitOutcome::RateLimited | SubmitOutcome::AlreadySolved => {
+            Style::default().fg(Color::Yellow)
+        }
+        _ => Style::default().fg(Color::Red),
+    }
+}
+
+pub fn draw_outputs<B>(f: &mut Frame<B>, output_rect: Rect, debug_rect: Rect, app: &mut App)
 where
     B: Backend,
 {
-    let text = if let Some(i) = app.input_highlight {
-        let day = app.days.get(app.day_highlight.unwrap()).unwrap();
-        let part = app.part_highlight.unwrap() + 1;
-        let path = day
-            .instances
-            .iter()
-            .filter(|i| i.part == part)
-            .nth(i)
-            .unwrap()
-            .input;
-
-        OpenOptions::new()
-            .read(true)
-            .write(false)
-            .open(path)
-            .map(|f| {
-                BufReader::new(f)
-                    .lines()
-                    .filter_map(|l| l.ok().map(Spans::from))
-                    .take(rect.height as usize)
-                    .collect::<Vec<_>>()
-            })
-            .ok()
-            .unwrap_or_else(Vec::new)
+    let answer = app.highlighted_instance().and_then(|i| {
+        i.answer.as_ref().map(|a| {
+            let mut lines = a
+                .to_string()
+                .split_terminator('\n')
+                .map(|l| Spans::from(l.to_owned()))
+                .collect::<Vec<_>>();
+
+            if i.submitting {
+                lines.push(Spans::from("Submitting..."));
+            } else if let Some(r) = &i.receipt {
+                lines.push(Spans::from(Span::styled(
+                    format!("Submitted: {}", r),
+                    receipt_style(r),
+                )));
+            }
+
+            lines
+        })
+    });
+
+    let output_rect = if let Some(answer) = answer {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length((answer.len() as u16 + 2).min(output_rect.height / 2)),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(output_rect);
+
+        let answer = Paragraph::new(answer)
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .block(Block::default().title("Answer").borders(Borders::ALL))
+            .alignment(Alignment::Left);
+
+        f.render_widget(answer, chunks[0]);
+        chunks[1]
     } else {
-        Vec::new()
+        output_rect
     };
 
+    draw_pane(f, output_rect, app, PaneKind::Output);
+    draw_pane(f, debug_rect, app, PaneKind::Debug);
+}
+
+/// A line with the matches of a search highlighted.
+fn highlight<'a>(line: &'a str, query: Option<&str>) -> Vec<Span<'a>> {
+    let query = match query {
+        Some(q) if !q.is_empty() && line.contains(q) => q,
+        _ => return vec![Span::raw(line)],
+    };
+
+    let style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let mut spans = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find(query) {
+        let end = start + query.len();
+        spans.push(Span::raw(&rest[..start]));
+        spans.push(Span::styled(&rest[start..end], style));
+        rest = &rest[end..];
+    }
+    spans.push(Span::raw(rest));
+
+    spans
+}
+
+/// The title of a pane: its name, the lines in view, and the search.
+fn pane_title(name: String, pane: &Pane, lines: &[&str], height: usize) -> String {
+    let offset = pane.offset(lines.len());
+
+    let mut title = name;
+    if lines.len() > height {
+        title.push_str(&format!(
+            " {}-{}/{}",
+            offset + 1,
+            (offset + height).min(lines.len()),
+            lines.len()
+        ));
+    }
+    if pane.column > 0 {
+        title.push_str(&format!(" col {}", pane.column + 1));
+    }
+    if pane.follow {
+        title.push_str(" [follow]");
+    }
+    if let Some(q) = pane.search.as_deref().filter(|q| !q.is_empty()) {
+        let matches = lines.iter().filter(|l| l.contains(q)).count();
+        title.push_str(&format!(" /{} ({} matches)", q, matches));
+    }
+
+    title
+}
+
+fn pane_border(app: &App, kind: PaneKind) -> Style {
+    if app.focus == kind {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    }
+}
+
+/// The Output or Debug pane of the highlighted instance, scrolled to its
+/// offset. Only the lines in view are laid out, as debug traces get long.
+pub fn draw_pane<B>(f: &mut Frame<B>, rect: Rect, app: &mut App, kind: PaneKind)
+where
+    B: Backend,
+{
+    if kind == PaneKind::Input {
+        return draw_input_viewer(f, rect, app);
+    }
+
+    let height = rect.height.saturating_sub(2) as usize;
+    app.pane_mut(kind).height = height;
+
+    let pane = app.pane(kind);
+    let lines = app
+        .pane_text(kind)
+        .split_terminator('\n')
+        .collect::<Vec<_>>();
+    let offset = pane.offset(lines.len());
+    let query = pane.search.as_deref();
+
+    let text = lines
+        .iter()
+        .skip(offset)
+        .take(height)
+        .map(|l| Spans::from(highlight(l, query)))
+        .collect::<Vec<_>>();
+
     let paragraph = Paragraph::new(text)
-        .style(Style::default())
         .block(
             Block::default()
-                .title("Input preview")
-                .borders(Borders::ALL),
+                .title(pane_title(kind.title().to_string(), pane, &lines, height))
+                .borders(Borders::ALL)
+                .border_style(pane_border(app, kind)),
         )
         .alignment(Alignment::Left);
-    // .wrap(Wrap { trim: true });
 
-    f.render_widget(paragraph, rect)
+    f.render_widget(paragraph, rect);
 }
 
-pub fn draw_outputs<B>(f: &mut Frame<B>, output_rect: Rect, debug_rect: Rect, app: &mut App)
+/// The input of the highlighted instance, read once, with line numbers and
+/// scrolled both ways, under a header with its size and shape.
+pub fn draw_input_viewer<B>(f: &mut Frame<B>, rect: Rect, app: &mut App)
 where
     B: Backend,
 {
-    let (output, debug) = if let Some(i) = app.day_highlight {
-        let d = app.days.get(i).unwrap();
-        if let Some(part) = app.part_highlight {
-            let part = part + 1;
-            if let Some(i) = app.input_highlight {
-                let i = d
-                    .instances
-                    .iter()
-                    .filter(|i| i.part == part)
-                    .nth(i)
-                    .unwrap();
+    app.load_input_view();
 
-                (
-                    i.output.split_terminator('\n').map(Spans::from).collect(),
-                    i.debug.split_terminator('\n').map(Spans::from).collect(),
-                )
-            } else {
-                (Vec::new(), Vec::new())
-            }
-        } else {
-            (Vec::new(), Vec::new())
+    let height = rect.height.saturating_sub(3) as usize;
+    app.pane_mut(PaneKind::Input).height = height;
+
+    let pane = app.pane(PaneKind::Input);
+    let instance = app.highlighted_instance();
+    let name = match instance {
+        Some(i) => format!("Input {}", i.input),
+        None => "Input".to_string(),
+    };
+
+    let (title, header, text) = match instance.and_then(|i| i.view.as_ref()) {
+        Some(Ok(view)) => {
+            let lines = view.text.split_terminator('\n').collect::<Vec<_>>();
+            let offset = pane.offset(lines.len());
+            let gutter = lines.len().to_string().len();
+            let query = pane.search.as_deref();
+
+            let text = lines
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(height)
+                .map(|(n, l)| {
+                    let l = l.trim_end_matches('\r');
+                    let start = l
+                        .char_indices()
+                        .nth(pane.column)
+                        .map_or(l.len(), |(i, _)| i);
+
+                    let mut spans = vec![Span::styled(
+                        format!("{:>width$} ", n + 1, width = gutter),
+                        Style::default().fg(Color::DarkGray),
+                    )];
+                    spans.extend(highlight(&l[start..], query));
+                    Spans::from(spans)
+                })
+                .collect::<Vec<_>>();
+
+            (
+                pane_title(name, pane, &lines, height),
+                Spans::from(Span::styled(
+                    view.summary(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                text,
+            )
         }
-    } else {
-        (Vec::new(), Vec::new())
+        Some(Err(e)) => (
+            name,
+            Spans::from(Span::styled(e.clone(), Style::default().fg(Color::Red))),
+            Vec::new(),
+        ),
+        None => (name, Spans::default(), Vec::new()),
     };
 
-    let output = Paragraph::new(output)
-        .style(Style::default())
-        .block(Block::default().title("Output").borders(Borders::ALL))
-        .alignment(Alignment::Left);
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(pane_border(app, PaneKind::Input));
+    let inner = block.inner(rect);
+    f.render_widget(block, rect);
 
-    let debug = Paragraph::new(debug)
-        .style(Style::default())
-        .block(Block::default().title("Debug").borders(Borders::ALL))
-        .alignment(Alignment::Left);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+        .split(inner);
 
-    f.render_widget(output, output_rect);
-    f.render_widget(debug, debug_rect);
+    f.render_widget(Paragraph::new(header), chunks[0]);
+    f.render_widget(Paragraph::new(text), chunks[1]);
 }
 
-pub fn draw<B>(f: &mut Frame<B>, app: &mut App)
+pub fn draw_years<B>(f: &mut Frame<B>, rect: Rect, app: &mut App)
+where
+    B: Backend,
+{
+    let titles = app
+        .years
+        .iter()
+        .map(|y| Spans::from(y.number.to_string()))
+        .collect();
+
+    let mut title = vec![Span::raw("Years (←/→)")];
+    if app.watcher.is_some() {
+        title.push(Span::styled(
+            " ◉ watching",
+            Style::default().fg(Color::Green),
+        ));
+    }
+    if app.build.is_some() {
+        let spinner = SPINNER[(app.tick / 4) % SPINNER.len()];
+        title.push(Span::styled(
+            format!(" {} building", spinner),
+            Style::default().fg(Color::Blue),
+        ));
+    } else if hot::is_loaded() {
+        title.push(Span::styled(
+            " ↻ reloaded",
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if let Some(notice) = &app.notice {
+        title.push(Span::raw(format!(" - {}", notice)));
+    }
+
+    let tabs = Tabs::new(titles)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Spans::from(title)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .select(app.year_highlight);
+
+    f.render_widget(tabs, rect);
+}
+
+/// The input being typed for the highlighted day, with the cursor in view.
+pub fn draw_editor<B>(f: &mut Frame<B>, rect: Rect, app: &mut App)
+where
+    B: Backend,
+{
+    let height = rect.height.saturating_sub(2) as usize;
+    let editor = match app.editor_mut() {
+        Some(e) => e,
+        None => return,
+    };
+    editor.scroll(height);
+
+    let text = editor
+        .lines
+        .iter()
+        .skip(editor.offset)
+        .take(height)
+        .map(|l| Spans::from(l.as_str()))
+        .collect::<Vec<_>>();
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title("Ad-hoc input (F1/F2: run part 1/2, Ctrl-S: save, Esc: close)")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(paragraph, rect);
+
+    if app.prompt.is_none() {
+        let editor = app.editor_mut().unwrap();
+        f.set_cursor(
+            (rect.x + 1 + editor.column as u16).min(rect.right().saturating_sub(2)),
+            rect.y + 1 + (editor.row - editor.offset) as u16,
+        );
+    }
+}
+
+/// A popup over the middle of the screen, with the line being typed.
+pub fn draw_prompt<B>(f: &mut Frame<B>, prompt: &Prompt)
 where
     B: Backend,
 {
     let size = f.size();
+    let width = (size.width * 3 / 5).max(20).min(size.width);
+    let rect = Rect::new(
+        size.x + (size.width - width) / 2,
+        size.y + size.height.saturating_sub(3) / 2,
+        width,
+        3.min(size.height),
+    );
+
+    let text = Paragraph::new(prompt.text.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} (Enter/Esc)", prompt.title))
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+
+    f.render_widget(Clear, rect);
+    f.render_widget(text, rect);
+    f.set_cursor(
+        (rect.x + 1 + prompt.text.chars().count() as u16).min(rect.right().saturating_sub(2)),
+        rect.y + 1,
+    );
+}
+
+pub fn draw<B>(f: &mut Frame<B>, app: &mut App)
+where
+    B: Backend,
+{
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(f.size());
+
+    draw_years(f, chunks[0], app);
+
+    if app.maximised {
+        draw_pane(f, chunks[1], app, app.focus);
+
+        if let Some(prompt) = &app.prompt {
+            draw_prompt(f, prompt);
+        }
+        return;
+    }
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
-        .split(size);
+        .split(chunks[1]);
 
     let main_chunk = chunks[0];
     let output_chunk = chunks[1];
+    let build_chunk = chunks[1];
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -301,6 +716,27 @@ where
 
     draw_list(f, days_chunk, app);
     draw_inputs(f, input_chunk, app);
-    draw_outputs(f, output_chunk, debug_chunk, app);
-    draw_input_preview(f, description_chunk, app)
+    if app.build_log.is_some() {
+        draw_pane(f, build_chunk, app, PaneKind::Build);
+    } else {
+        draw_outputs(f, output_chunk, debug_chunk, app);
+    }
+
+    if app.editing {
+        draw_editor(f, description_chunk, app);
+    } else if let Some(report) = app.highlighted_instance().and_then(|i| i.bench.clone()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(7)].as_ref())
+            .split(description_chunk);
+
+        draw_input_viewer(f, chunks[0], app);
+        draw_bench(f, chunks[1], &report);
+    } else {
+        draw_input_viewer(f, description_chunk, app)
+    }
+
+    if let Some(prompt) = &app.prompt {
+        draw_prompt(f, prompt);
+    }
 }
@@ -4,11 +4,23 @@ use tui::Terminal;
 
 mod app;
 use app::*;
+pub use app::{Exit, Options, Resume};
 
+#[allow(dead_code)]
 mod widget;
 use widget::*;
 
+mod editor;
+use editor::*;
 mod layout;
+mod pane;
+use pane::*;
+mod viewer;
+use viewer::*;
+mod watch;
+use watch::*;
+mod prompt;
+use prompt::*;
 mod threadpool;
 use threadpool::*;
 
@@ -53,7 +65,7 @@ mod implementation {
         rx
     }
 
-    pub fn run() -> eyre::Result<()> {
+    pub fn run(options: Options) -> eyre::Result<Exit> {
         let stdout = io::stdout().into_raw_mode()?;
         let stdout = MouseTerminal::from(stdout);
         let stdout = AlternateScreen::from(stdout);
@@ -64,7 +76,7 @@ mod implementation {
 
         let tick_rate = Duration::from_millis(16);
 
-        let mut app = App::default();
+        let mut app = App::new(options);
 
         let events = events(tick_rate);
 
@@ -75,15 +87,29 @@ mod implementation {
                 Event::Input(key) => match key {
                     Key::Char('\n') => app.on_enter(),
                     Key::Char(c) => app.handle_key(c),
+                    Key::Ctrl(c) => app.on_ctrl(c),
+                    Key::F(n) => app.on_function(n),
                     Key::Up => app.on_up(),
                     Key::Down => app.on_down(),
+                    Key::Left => app.on_left(),
+                    Key::Right => app.on_right(),
+                    Key::Backspace => app.on_backspace(),
+                    Key::Esc => app.on_escape(),
+                    Key::PageUp => app.on_page_up(),
+                    Key::PageDown => app.on_page_down(),
+                    Key::Home => app.on_home(),
+                    Key::End => app.on_end(),
                     _ => {}
                 },
                 Event::Tick => app.on_tick(),
             }
 
+            if let Some(resume) = app.restart() {
+                break Ok(Exit::Restart(resume));
+            }
+
             if app.should_quit() {
-                break Ok(());
+                break Ok(Exit::Quit);
             }
         }
     }
@@ -93,10 +119,10 @@ mod implementation {
 
 mod implementation {
     use super::*;
-    use crossterm::event::{self, Event, KeyCode};
+    use crossterm::event::{self, Event, KeyCode, KeyModifiers};
     use tui::backend::CrosstermBackend;
 
-    pub fn run() -> eyre::Result<()> {
+    pub fn run(options: Options) -> eyre::Result<Exit> {
         let stdout = io::stdout();
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
@@ -106,7 +132,7 @@ mod implementation {
 
         let mut last_tick = Instant::now();
 
-        let mut app = App::default();
+        let mut app = App::new(options);
 
         loop {
             terminal.draw(|f| layout::draw(f, &mut app))?;
@@ -118,10 +144,23 @@ mod implementation {
             if crossterm::event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
                     match key.code {
+                        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.on_ctrl(c)
+                        }
                         KeyCode::Char(c) => app.handle_key(c),
+                        KeyCode::F(n) => app.on_function(n),
                         KeyCode::Up => app.on_up(),
                         KeyCode::Down => app.on_down(),
+                        KeyCode::Left => app.on_left(),
+                        KeyCode::Right => app.on_right(),
                         KeyCode::Enter => app.on_enter(),
+                        KeyCode::Backspace => app.on_backspace(),
+                        KeyCode::Esc => app.on_escape(),
+                        KeyCode::Tab => app.handle_key('\t'),
+                        KeyCode::PageUp => app.on_page_up(),
+                        KeyCode::PageDown => app.on_page_down(),
+                        KeyCode::Home => app.on_home(),
+                        KeyCode::End => app.on_end(),
                         _ => {}
                     }
                 }
@@ -132,8 +171,12 @@ mod implementation {
                 last_tick = Instant::now();
             }
 
+            if let Some(resume) = app.restart() {
+                break Ok(Exit::Restart(resume));
+            }
+
             if app.should_quit() {
-                break Ok(());
+                break Ok(Exit::Quit);
             }
         }
     }
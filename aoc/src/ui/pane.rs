@@ -0,0 +1,282 @@
+/// The panes that show text: those under the lists, and the input viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneKind {
+    Output,
+    Debug,
+    Input,
+    /// The compiler output of a failed reload.
+    Build,
+}
+
+impl PaneKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            PaneKind::Output => "Output",
+            PaneKind::Debug => "Debug",
+            PaneKind::Input => "Input",
+            PaneKind::Build => "Build failed (r: rebuild, Esc: dismiss)",
+        }
+    }
+
+    /// The pane that gets the focus after this one.
+    pub fn next(&self) -> Self {
+        match self {
+            PaneKind::Output => PaneKind::Debug,
+            PaneKind::Debug => PaneKind::Input,
+            PaneKind::Input => PaneKind::Build,
+            PaneKind::Build => PaneKind::Output,
+        }
+    }
+}
+
+/// Scroll and search state of a text pane.
+#[derive(Debug, Clone)]
+pub struct Pane {
+    offset: usize,
+    /// First column in view, for panes that scroll sideways.
+    pub(crate) column: usize,
+    /// Keeps the last lines in view as more text comes in.
+    pub(crate) follow: bool,
+    /// Number of lines that fit in the pane when it was last drawn.
+    pub(crate) height: usize,
+    pub(crate) search: Option<String>,
+    /// Line of the match last brought in view, which may not be the first
+    /// line in view near the end of the text.
+    matched: Option<usize>,
+}
+
+impl Default for Pane {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            column: 0,
+            follow: true,
+            height: 0,
+            search: None,
+            matched: None,
+        }
+    }
+}
+
+impl Pane {
+    /// A pane that stays where it is scrolled to, for text that doesn't grow.
+    pub fn fixed() -> Self {
+        Self {
+            follow: false,
+            ..Self::default()
+        }
+    }
+
+    /// First line in view, out of `lines`.
+    pub fn offset(&self, lines: usize) -> usize {
+        let last = lines.saturating_sub(self.height);
+
+        if self.follow {
+            last
+        } else {
+            self.offset.min(last)
+        }
+    }
+
+    pub fn scroll_up(&mut self, n: usize, lines: usize) {
+        self.offset = self.offset(lines).saturating_sub(n);
+        self.follow = false;
+        self.matched = None;
+    }
+
+    /// Scrolls down, and follows the text again once at the bottom.
+    pub fn scroll_down(&mut self, n: usize, lines: usize) {
+        let last = lines.saturating_sub(self.height);
+        self.offset = (self.offset(lines) + n).min(last);
+        self.follow = self.offset == last;
+        self.matched = None;
+    }
+
+    pub fn scroll_left(&mut self, n: usize) {
+        self.column = self.column.saturating_sub(n);
+    }
+
+    /// Scrolls right, as long as some of the widest line stays in view.
+    pub fn scroll_right(&mut self, n: usize, width: usize) {
+        self.column = (self.column + n).min(width.saturating_sub(1));
+    }
+
+    pub fn page_up(&mut self, lines: usize) {
+        self.scroll_up(self.height.max(1), lines);
+    }
+
+    pub fn page_down(&mut self, lines: usize) {
+        self.scroll_down(self.height.max(1), lines);
+    }
+
+    pub fn home(&mut self) {
+        self.offset = 0;
+        self.column = 0;
+        self.follow = false;
+        self.matched = None;
+    }
+
+    pub fn end(&mut self) {
+        self.follow = true;
+        self.matched = None;
+    }
+
+    /// Brings the next line that matches the search in view, after the first
+    /// line in view or before it, wrapping around the text. With `inclusive`,
+    /// the first line in view may be the match, as when the search is being
+    /// typed.
+    pub fn find(&mut self, text: &str, forward: bool, inclusive: bool) {
+        let query = match self.search.as_deref() {
+            Some(q) if !q.is_empty() => q,
+            _ => return,
+        };
+
+        let lines = text.split_terminator('\n').count();
+        let current = match self.matched {
+            Some(m) if !inclusive => m,
+            _ => self.offset(lines),
+        };
+        let matches = text
+            .split_terminator('\n')
+            .enumerate()
+            .filter(|(_, l)| l.contains(query))
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>();
+
+        let found = if forward {
+            matches
+                .iter()
+                .find(|n| **n > current || (inclusive && **n == current))
+                .or_else(|| matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|n| **n < current)
+                .or_else(|| matches.last())
+        };
+
+        if let Some(n) = found {
+            self.offset = *n;
+            self.follow = false;
+            self.matched = Some(*n);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pane showing `height` lines, scrolled to `offset`.
+    fn pane(height: usize, offset: usize) -> Pane {
+        Pane {
+            height,
+            offset,
+            ..Pane::fixed()
+        }
+    }
+
+    fn text(lines: usize) -> String {
+        (0..lines).map(|n| format!("line {}\n", n)).collect()
+    }
+
+    #[test]
+    fn panes_follow_the_end_until_scrolled() {
+        let mut pane = Pane {
+            height: 10,
+            ..Pane::default()
+        };
+        assert_eq!(pane.offset(5), 0);
+        assert_eq!(pane.offset(25), 15);
+
+        pane.scroll_up(3, 25);
+        assert_eq!(pane.offset(25), 12);
+        assert_eq!(pane.offset(40), 12);
+
+        // Back at the bottom, it follows again.
+        pane.scroll_down(100, 40);
+        assert!(pane.follow);
+        assert_eq!(pane.offset(50), 40);
+    }
+
+    #[test]
+    fn scrolling_stays_within_the_text() {
+        let mut pane = pane(10, 0);
+
+        pane.scroll_up(5, 30);
+        assert_eq!(pane.offset(30), 0);
+
+        pane.page_down(30);
+        pane.page_down(30);
+        pane.page_down(30);
+        assert_eq!(pane.offset(30), 20);
+
+        pane.scroll_right(50, 20);
+        assert_eq!(pane.column, 19);
+        pane.scroll_left(50);
+        assert_eq!(pane.column, 0);
+
+        pane.home();
+        assert_eq!(pane.offset(30), 0);
+        pane.end();
+        assert_eq!(pane.offset(30), 20);
+    }
+
+    #[test]
+    fn find_moves_between_matches_and_wraps() {
+        let text = text(30);
+        let mut pane = pane(5, 0);
+        pane.search = Some(String::from("line 2"));
+
+        // Typing the search may match the first line in view.
+        pane.find(&text, true, true);
+        assert_eq!(pane.offset(30), 2);
+
+        pane.find(&text, true, false);
+        assert_eq!(pane.offset(30), 20);
+        pane.find(&text, true, false);
+        assert_eq!(pane.offset(30), 21);
+
+        pane.find(&text, false, false);
+        assert_eq!(pane.offset(30), 20);
+        pane.find(&text, false, false);
+        pane.find(&text, false, false);
+        assert_eq!(pane.matched, Some(29));
+        assert_eq!(pane.offset(30), 25);
+    }
+
+    #[test]
+    fn find_goes_past_the_last_page() {
+        let text = text(30);
+        let mut pane = pane(10, 0);
+        pane.search = Some(String::from("line 2"));
+
+        // Lines 2 and 20 to 25.
+        for _ in 0..7 {
+            pane.find(&text, true, false);
+        }
+        assert_eq!(pane.matched, Some(25));
+        assert_eq!(pane.offset(30), 20);
+
+        // The last page starts at line 20, but the next match is after 25.
+        pane.find(&text, true, false);
+        assert_eq!(pane.matched, Some(26));
+        assert_eq!(pane.offset(30), 20);
+    }
+
+    #[test]
+    fn find_without_a_match_stays() {
+        let text = text(30);
+        let mut pane = pane(5, 7);
+
+        pane.search = Some(String::new());
+        pane.find(&text, true, true);
+        assert_eq!(pane.offset(30), 7);
+
+        pane.search = Some(String::from("missing"));
+        pane.find(&text, true, false);
+        assert_eq!(pane.offset(30), 7);
+        assert!(pane.matched.is_none());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::ui::PaneKind;
+
+/// What the line typed in a prompt is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptAction {
+    /// Writes the reports of the current year to the path.
+    Export,
+    /// Searches a pane as the text is typed.
+    Search(PaneKind),
+    /// Saves the input typed in the editor to the path.
+    SaveInput,
+}
+
+/// A line of text typed in a popup, submitted with Enter and dismissed with
+/// Esc.
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    pub(crate) title: String,
+    pub(crate) text: String,
+    pub(crate) action: PromptAction,
+}
+
+impl Prompt {
+    pub fn new(title: impl Into<String>, text: impl Into<String>, action: PromptAction) -> Self {
+        Self {
+            title: title.into(),
+            text: text.into(),
+            action,
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    pub fn pop(&mut self) {
+        self.text.pop();
+    }
+}
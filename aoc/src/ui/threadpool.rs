@@ -1,14 +1,125 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, Once};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-type JobResult = Result<Duration, color_eyre::Report>;
+use crate::{AocError, CancelToken, Solution};
+
+type JobResult = Result<Solution, color_eyre::Report>;
+
+const WORKER_NAME: &str = "aoc-worker";
+
+/// How long a job gets to notice its cancellation before its worker is
+/// abandoned and replaced.
+const CANCEL_GRACE: Duration = Duration::from_secs(1);
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<JobPanic>> = const { RefCell::new(None) };
+    /// Whether the thread is a worker, whose panics are caught.
+    static IS_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// The last panic of a thread other than a worker, such as one a job spawned,
+/// and when it happened.
+static OTHER_PANIC: Mutex<Option<(Instant, JobPanic)>> = Mutex::new(None);
+
+/// A panic caught while running a job.
+#[derive(Debug, Clone)]
+pub struct JobPanic {
+    pub message: String,
+    pub location: Option<String>,
+    /// A panic of another thread while the job ran, likely one it spawned and
+    /// whose panic it passed on.
+    pub thread: Option<Box<JobPanic>>,
+}
+
+impl JobPanic {
+    fn new(info: &panic::PanicHookInfo) -> Self {
+        JobPanic {
+            message: payload_message(info.payload()),
+            location: info.location().map(|l| l.to_string()),
+            thread: None,
+        }
+    }
+
+    /// The panic the worker just caught, from a job started at `started`.
+    fn caught(payload: Box<dyn Any + Send>, started: Instant) -> Self {
+        let mut panic = LAST_PANIC
+            .with(|p| p.borrow_mut().take())
+            .unwrap_or_else(|| JobPanic {
+                message: payload_message(&*payload),
+                location: None,
+                thread: None,
+            });
+
+        panic.thread = OTHER_PANIC
+            .lock()
+            .unwrap()
+            .take()
+            .filter(|(at, _)| *at >= started)
+            .map(|(_, p)| Box::new(p));
+        panic
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
+
+impl fmt::Display for JobPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(l) => write!(f, "panicked at {}: {}", l, self.message)?,
+            None => write!(f, "panicked: {}", self.message)?,
+        }
+        match &self.thread {
+            Some(p) => write!(f, ", after a thread {}", p),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for JobPanic {}
+
+/// Records panics of workers instead of printing them over the TUI. Panics of
+/// other threads are passed on to the previous hook, and also kept for a job
+/// that may have spawned the thread.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let panic = JobPanic::new(info);
+
+            if IS_WORKER.with(Cell::get) {
+                LAST_PANIC.with(|p| *p.borrow_mut() = Some(panic));
+            } else {
+                *OTHER_PANIC.lock().unwrap() = Some((Instant::now(), panic));
+                previous(info)
+            }
+        }));
+    });
+}
 
 pub struct Worker {
     id: usize,
-    job: Option<usize>,
+    job: Option<RunningJob>,
+    abandoned: bool,
     transmitter: Sender<Job>,
     receiver: Receiver<JobResult>,
     quit_tx: Sender<()>,
@@ -21,23 +132,42 @@ impl Worker {
         let (status_tx, status_rx) = mpsc::channel();
         let (quit_tx, quit_rx) = mpsc::channel();
 
-        let handle = thread::spawn(move || loop {
-            match quit_rx.try_recv() {
-                Ok(_) => break,
-                Err(mpsc::TryRecvError::Disconnected) => break,
-                Err(_) => {}
-            };
-
-            match data_rx.recv_timeout(Duration::from_millis(16)) {
-                Ok(f) => status_tx.send((f.closure)()).unwrap(),
-                Err(mpsc::RecvTimeoutError::Timeout) => {}
-                Err(mpsc::RecvTimeoutError::Disconnected) => panic!("Disconnected"),
-            }
-        });
+        let handle = thread::Builder::new()
+            .name(format!("{}-{}", WORKER_NAME, id))
+            .spawn(move || loop {
+                IS_WORKER.with(|w| w.set(true));
+
+                match quit_rx.try_recv() {
+                    Ok(_) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => break,
+                    Err(_) => {}
+                };
+
+                match data_rx.recv_timeout(Duration::from_millis(16)) {
+                    Ok(f) => {
+                        let closure = f.closure;
+                        let cancel = f.cancel;
+                        let started = Instant::now();
+                        let result = panic::catch_unwind(AssertUnwindSafe(|| closure(cancel)))
+                            .unwrap_or_else(|payload| {
+                                let panic = JobPanic::caught(payload, started);
+                                Err(color_eyre::Report::new(panic))
+                            });
+
+                        if status_tx.send(result).is_err() {
+                            break;
+                        }
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .unwrap();
 
         Self {
             id,
             job: None,
+            abandoned: false,
             transmitter: data_tx,
             receiver: status_rx,
             quit_tx,
@@ -49,43 +179,120 @@ impl Worker {
         self.job.is_some()
     }
 
+    /// Whether the thread of the worker is gone or was given up on, and the
+    /// worker must be replaced.
+    pub fn is_dead(&self) -> bool {
+        self.abandoned
+            || self
+                .handle
+                .as_ref()
+                .map(|h| h.is_finished())
+                .unwrap_or(true)
+    }
+
+    pub fn job_id(&self) -> Option<usize> {
+        self.job.as_ref().map(|j| j.id)
+    }
+
     pub fn run_job(&mut self, job: Job) -> Result<usize, ()> {
         if self.is_running() {
             Err(())
         } else {
             let id = job.id;
-            self.job = Some(id);
+            self.job = Some(RunningJob {
+                id,
+                cancel: job.cancel.clone(),
+                started: Instant::now(),
+                timeout: job.timeout,
+                stop: None,
+            });
             self.transmitter.send(job).unwrap();
             Ok(id)
         }
     }
 
+    /// Asks the running job to stop, for the given reason.
+    pub fn stop(&mut self, reason: AocError) {
+        if let Some(job) = self.job.as_mut() {
+            if job.stop.is_none() {
+                job.cancel.cancel();
+                job.stop = Some((Instant::now(), reason));
+            }
+        }
+    }
+
     pub fn update(&mut self) -> Option<(usize, JobResult)> {
         match self.receiver.try_recv() {
-            Ok(result) => self.job.take().map(|i| (i, result)),
-            Err(mpsc::TryRecvError::Disconnected) => panic!("Disconnected"),
-            Err(mpsc::TryRecvError::Empty) => None,
+            Ok(result) => self.job.take().map(|job| {
+                let result = match (job.stop, result) {
+                    (Some((_, reason)), Err(_)) => Err(color_eyre::Report::new(reason)),
+                    (_, result) => result,
+                };
+                (job.id, result)
+            }),
+            Err(mpsc::TryRecvError::Disconnected) => self.job.take().map(|job| {
+                let panic = JobPanic {
+                    message: format!("worker {} stopped while running the job", self.id),
+                    location: None,
+                    thread: None,
+                };
+                (job.id, Err(color_eyre::Report::new(panic)))
+            }),
+            Err(mpsc::TryRecvError::Empty) => {
+                let job = self.job.as_ref()?;
+
+                match job.stop {
+                    Some((since, reason)) if since.elapsed() >= CANCEL_GRACE => {
+                        // The job ignores its cancellation: leave its thread to
+                        // finish on its own, and let the pool replace the worker.
+                        self.abandoned = true;
+                        let job = self.job.take().unwrap();
+                        Some((job.id, Err(color_eyre::Report::new(reason))))
+                    }
+                    None if job
+                        .timeout
+                        .map(|t| job.started.elapsed() >= t)
+                        .unwrap_or(false) =>
+                    {
+                        self.stop(AocError::TimedOut);
+                        None
+                    }
+                    _ => None,
+                }
+            }
         }
     }
 }
 
-type BoxedFn = Box<dyn FnOnce() -> JobResult + Send + 'static>;
+struct RunningJob {
+    id: usize,
+    cancel: CancelToken,
+    started: Instant,
+    timeout: Option<Duration>,
+    stop: Option<(Instant, AocError)>,
+}
+
+type BoxedFn = Box<dyn FnOnce(CancelToken) -> JobResult + Send + 'static>;
 
 static JOB_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
 pub struct Job {
     id: usize,
     closure: BoxedFn,
+    cancel: CancelToken,
+    timeout: Option<Duration>,
 }
 
 impl Job {
-    pub fn new<F>(f: F) -> Self
+    pub fn new<F>(f: F, timeout: Option<Duration>) -> Self
     where
-        F: FnOnce() -> JobResult + Send + 'static,
+        F: FnOnce(CancelToken) -> JobResult + Send + 'static,
     {
         Job {
             id: JOB_ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             closure: Box::new(f),
+            cancel: CancelToken::new(),
+            timeout,
         }
     }
 }
@@ -98,27 +305,37 @@ pub struct UpdateReport {
 pub struct ThreadPool<const THREADS: usize> {
     workers: Vec<Worker>,
     jobs: VecDeque<Job>,
+    cancelled: Vec<usize>,
 }
 
 impl<const THREADS: usize> ThreadPool<THREADS> {
     pub fn new() -> Self {
+        install_panic_hook();
+
         let mut workers = Vec::with_capacity(THREADS);
         workers.extend((0..THREADS).map(Worker::new));
         Self {
             workers,
             jobs: VecDeque::new(),
+            cancelled: Vec::new(),
         }
     }
 
+    /// A worker free to run a job. Dead ones are left for the next update to
+    /// replace.
     fn next_available(&mut self) -> Option<&mut Worker> {
-        self.workers.iter_mut().find(|w| w.is_running())
+        self.workers
+            .iter_mut()
+            .find(|w| !w.is_running() && !w.is_dead())
     }
 
-    pub fn register<F>(&mut self, f: F) -> (usize, Option<usize>)
+    /// Queues a job, which is handed a token telling it when to stop. If it
+    /// runs for longer than `timeout`, the token is triggered for it.
+    pub fn register<F>(&mut self, f: F, timeout: Option<Duration>) -> (usize, Option<usize>)
     where
-        F: FnOnce() -> Result<Duration, color_eyre::Report> + Send + 'static,
+        F: FnOnce(CancelToken) -> JobResult + Send + 'static,
     {
-        let job = Job::new(f);
+        let job = Job::new(f, timeout);
         let job_id = job.id;
         if let Some(w) = self.next_available() {
             let worker_id = w.id;
@@ -131,8 +348,34 @@ impl<const THREADS: usize> ThreadPool<THREADS> {
         }
     }
 
+    /// Cancels a job: a waiting job is dropped, a running one is asked to stop.
+    /// Either way, it is reported as finished by a later update.
+    pub fn cancel(&mut self, job_id: usize) {
+        if let Some(idx) = self.jobs.iter().position(|j| j.id == job_id) {
+            self.jobs.remove(idx);
+            self.cancelled.push(job_id);
+        } else if let Some(w) = self.workers.iter_mut().find(|w| w.job_id() == Some(job_id)) {
+            w.stop(AocError::Cancelled);
+        }
+    }
+
     pub fn update(&mut self) -> UpdateReport {
-        let finished_jobs = self.workers.iter_mut().filter_map(|w| w.update()).collect();
+        let mut finished_jobs = self
+            .workers
+            .iter_mut()
+            .filter_map(|w| w.update())
+            .collect::<Vec<_>>();
+
+        finished_jobs.extend(
+            self.cancelled
+                .drain(..)
+                .map(|id| (id, Err(color_eyre::Report::new(AocError::Cancelled)))),
+        );
+
+        self.workers
+            .iter_mut()
+            .filter(|w| w.is_dead() && !w.is_running())
+            .for_each(|w| *w = Worker::new(w.id));
 
         let started_jobs = self
             .workers
@@ -157,8 +400,196 @@ impl<const THREADS: usize> ThreadPool<THREADS> {
 impl<const THREADS: usize> Drop for ThreadPool<THREADS> {
     fn drop(&mut self) {
         self.workers.iter_mut().for_each(|w| {
-            w.quit_tx.send(()).unwrap();
-            w.handle.take().unwrap().join().unwrap();
+            let _ = w.quit_tx.send(());
+            // A worker still busy with a job could take forever: stop it, but
+            // don't wait for it.
+            w.stop(AocError::Cancelled);
+            if let Some(handle) = w.handle.take().filter(|_| !w.is_running()) {
+                let _ = handle.join();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Answer, Verdict};
+
+    fn solution(n: i128) -> JobResult {
+        Ok(Solution {
+            answer: Answer::Integer(n),
+            verdict: Verdict::Unknown,
+            parse: Duration::ZERO,
+            duration: Duration::ZERO,
+        })
+    }
+
+    /// Updates the pool until the job is finished, and returns its result.
+    fn wait<const N: usize>(pool: &mut ThreadPool<N>, job_id: usize) -> JobResult {
+        let start = Instant::now();
+        loop {
+            let report = pool.update();
+            if let Some((_, result)) = report
+                .finished_jobs
+                .into_iter()
+                .find(|(id, _)| *id == job_id)
+            {
+                return result;
+            }
+
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "job never finished"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn answer(result: JobResult) -> Answer {
+        result.unwrap().answer
+    }
+
+    #[test]
+    fn panics_become_failed_results() {
+        let mut pool = ThreadPool::<1>::new();
+
+        let (id, _) = pool.register(|_| panic!("no solution for {}", 7), None);
+        let error = wait(&mut pool, id).unwrap_err();
+        let panic = error.downcast_ref::<JobPanic>().unwrap();
+
+        assert_eq!(panic.message, "no solution for 7");
+        assert!(panic.location.as_ref().unwrap().contains("threadpool.rs"));
+        assert!(!pool.workers[0].is_dead());
+
+        let (id, _) = pool.register(|_| solution(1), None);
+        assert_eq!(answer(wait(&mut pool, id)), Answer::Integer(1));
+    }
+
+    #[test]
+    fn panics_of_threads_of_jobs_are_kept() {
+        let mut pool = ThreadPool::<1>::new();
+
+        let (id, _) = pool.register(
+            |_| {
+                thread::spawn(|| panic!("inner"))
+                    .join()
+                    .map_err(|_| color_eyre::eyre::eyre!("unreachable"))
+                    .unwrap();
+                solution(0)
+            },
+            None,
+        );
+        let error = wait(&mut pool, id).unwrap_err();
+        let panic = error.downcast_ref::<JobPanic>().unwrap();
+
+        assert!(panic.message.starts_with("called `Result::unwrap()`"));
+        assert_eq!(panic.thread.as_ref().unwrap().message, "inner");
+    }
+
+    #[test]
+    fn dead_workers_are_respawned() {
+        let mut pool = ThreadPool::<1>::new();
+        let _ = pool.workers[0].quit_tx.send(());
+
+        let start = Instant::now();
+        while !pool.workers[0].is_dead() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let (id, _) = pool.register(|_| solution(2), None);
+        assert_eq!(answer(wait(&mut pool, id)), Answer::Integer(2));
+        assert!(!pool.workers[0].is_dead());
+    }
+
+    fn reason(result: JobResult) -> AocError {
+        *result.unwrap_err().downcast_ref::<AocError>().unwrap()
+    }
+
+    /// A job that only stops when cancelled, as parts checking their token do.
+    fn cooperative(cancel: CancelToken) -> JobResult {
+        while !cancel.is_cancelled() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        Err(color_eyre::Report::new(AocError::Cancelled))
+    }
+
+    /// A job that never checks its token, and only stops once told through the
+    /// returned sender.
+    fn stubborn() -> (Sender<()>, impl FnOnce(CancelToken) -> JobResult) {
+        let (tx, rx) = mpsc::channel::<()>();
+        (tx, move |_| {
+            let _ = rx.recv();
+            solution(0)
         })
     }
+
+    #[test]
+    fn waiting_jobs_are_cancelled() {
+        let mut pool = ThreadPool::<1>::new();
+        let (release, job) = stubborn();
+        let (running, _) = pool.register(job, None);
+        let (waiting, worker) = pool.register(|_| solution(1), None);
+        assert_eq!(worker, None);
+
+        pool.cancel(waiting);
+        assert_eq!(reason(wait(&mut pool, waiting)), AocError::Cancelled);
+
+        release.send(()).unwrap();
+        assert_eq!(answer(wait(&mut pool, running)), Answer::Integer(0));
+    }
+
+    #[test]
+    fn running_jobs_are_cancelled() {
+        let mut pool = ThreadPool::<1>::new();
+        let (id, _) = pool.register(cooperative, None);
+        let start = Instant::now();
+
+        pool.cancel(id);
+        assert_eq!(reason(wait(&mut pool, id)), AocError::Cancelled);
+        assert!(start.elapsed() < CANCEL_GRACE);
+        assert!(!pool.workers[0].is_dead());
+    }
+
+    #[test]
+    fn jobs_time_out() {
+        let mut pool = ThreadPool::<1>::new();
+        let (id, _) = pool.register(cooperative, Some(Duration::from_millis(10)));
+
+        assert_eq!(reason(wait(&mut pool, id)), AocError::TimedOut);
+        assert!(!pool.workers[0].is_dead());
+    }
+
+    #[test]
+    fn stubborn_jobs_are_abandoned() {
+        let mut pool = ThreadPool::<1>::new();
+        let (release, job) = stubborn();
+        let (id, _) = pool.register(job, Some(Duration::from_millis(10)));
+        let start = Instant::now();
+
+        assert_eq!(reason(wait(&mut pool, id)), AocError::TimedOut);
+        assert!(start.elapsed() >= CANCEL_GRACE);
+
+        // The worker was replaced, and runs the next job while the thread of
+        // the old one is still stuck.
+        let (id, worker) = pool.register(|_| solution(3), None);
+        assert_eq!(worker, Some(0));
+        assert_eq!(answer(wait(&mut pool, id)), Answer::Integer(3));
+
+        let _ = release.send(());
+    }
+
+    #[test]
+    fn stubborn_jobs_are_cancelled() {
+        let mut pool = ThreadPool::<1>::new();
+        let (release, job) = stubborn();
+        let (id, _) = pool.register(job, None);
+
+        pool.cancel(id);
+        assert_eq!(reason(wait(&mut pool, id)), AocError::Cancelled);
+
+        let _ = release.send(());
+    }
 }
@@ -0,0 +1,208 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use color_eyre::eyre::{self, WrapErr};
+
+/// How an input is laid out, as far as can be told from its lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Empty,
+    /// Lines of the same width, without spaces.
+    Grid {
+        width: usize,
+        height: usize,
+    },
+    /// An integer on each line.
+    Ints,
+    /// A single line of comma-separated integers.
+    IntList(usize),
+    /// Blocks of lines separated by blank lines.
+    Groups(usize),
+    Lines,
+}
+
+impl Shape {
+    pub fn detect(lines: &[&str]) -> Self {
+        let trimmed = {
+            let end = lines
+                .iter()
+                .rposition(|l| !l.trim().is_empty())
+                .map_or(0, |n| n + 1);
+            &lines[..end]
+        };
+
+        if trimmed.is_empty() {
+            return Shape::Empty;
+        }
+
+        // Leading zeros are more likely digits of a grid than numbers.
+        let is_int = |s: &str| {
+            let s = s.trim();
+            s.parse::<i64>().is_ok() && (s == "0" || !s.starts_with('0'))
+        };
+
+        let blanks = trimmed.iter().filter(|l| l.trim().is_empty()).count();
+        if blanks > 0 {
+            let groups = trimmed
+                .split(|l| l.trim().is_empty())
+                .filter(|g| !g.is_empty())
+                .count();
+            return Shape::Groups(groups);
+        }
+
+        if trimmed.iter().all(|l| is_int(l)) {
+            return Shape::Ints;
+        }
+
+        if let [line] = trimmed {
+            if line.contains(',') && line.split(',').all(is_int) {
+                return Shape::IntList(line.split(',').count());
+            }
+        }
+
+        let width = trimmed[0].chars().count();
+        if trimmed.len() > 1
+            && width > 1
+            && trimmed
+                .iter()
+                .all(|l| l.chars().count() == width && !l.contains(' '))
+        {
+            return Shape::Grid {
+                width,
+                height: trimmed.len(),
+            };
+        }
+
+        Shape::Lines
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shape::Empty => write!(f, "empty"),
+            Shape::Grid { width, height } => write!(f, "grid {}x{}", width, height),
+            Shape::Ints => write!(f, "list of ints"),
+            Shape::IntList(n) => write!(f, "{} comma-separated ints", n),
+            Shape::Groups(n) => write!(f, "{} blank-line separated groups", n),
+            Shape::Lines => write!(f, "lines"),
+        }
+    }
+}
+
+/// An input file, read once for the viewer.
+#[derive(Debug, Clone)]
+pub struct InputView {
+    pub(crate) text: String,
+    pub(crate) lines: usize,
+    /// Width of the longest line, in characters.
+    pub(crate) width: usize,
+    pub(crate) shape: Shape,
+}
+
+impl InputView {
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+
+        let lines = text.lines().collect::<Vec<_>>();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let shape = Shape::detect(&lines);
+        let lines = lines.len();
+
+        Ok(Self {
+            text,
+            lines,
+            width,
+            shape,
+        })
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} line{}, max width {}, {}",
+            self.lines,
+            if self.lines == 1 { "" } else { "s" },
+            self.width,
+            self.shape
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fetch::tests::temp_dir;
+
+    fn detect(text: &str) -> Shape {
+        Shape::detect(&text.lines().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn shapes_of_inputs() {
+        assert_eq!(detect(""), Shape::Empty);
+        assert_eq!(detect("\n  \n"), Shape::Empty);
+        assert_eq!(detect("199\n200\n-3\n"), Shape::Ints);
+        assert_eq!(detect("3,4,3,1,2\n"), Shape::IntList(5));
+        assert_eq!(
+            detect("#..\n.#.\n..#\n"),
+            Shape::Grid {
+                width: 3,
+                height: 3
+            }
+        );
+        assert_eq!(detect("1\n2\n\n3\n\n\n4\n"), Shape::Groups(3));
+        assert_eq!(detect("forward 5\ndown 5\n"), Shape::Lines);
+    }
+
+    #[test]
+    fn digits_with_leading_zeros_are_grids() {
+        assert_eq!(
+            detect("00100\n11110\n10110\n"),
+            Shape::Grid {
+                width: 5,
+                height: 3
+            }
+        );
+        assert_eq!(detect("0\n10\n"), Shape::Ints);
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored() {
+        assert_eq!(detect("1\n2\n\n\n"), Shape::Ints);
+        assert_eq!(
+            detect("ab\ncd\n \n"),
+            Shape::Grid {
+                width: 2,
+                height: 2
+            }
+        );
+    }
+
+    #[test]
+    fn uneven_or_single_lines_are_lines() {
+        assert_eq!(detect("abc\nab\n"), Shape::Lines);
+        assert_eq!(detect("a b\nc d\n"), Shape::Lines);
+        assert_eq!(detect("abc\n"), Shape::Lines);
+        assert_eq!(detect("1,x,3\n"), Shape::Lines);
+    }
+
+    #[test]
+    fn views_sum_up_their_input() {
+        let dir = temp_dir("viewer");
+        let path = dir.join("input.txt");
+        fs::write(&path, "199\n200\n2080\n").unwrap();
+
+        let view = InputView::load(&path).unwrap();
+        assert_eq!(view.summary(), "3 lines, max width 4, list of ints");
+
+        fs::write(&path, "3,4,3").unwrap();
+        let view = InputView::load(&path).unwrap();
+        assert_eq!(
+            view.summary(),
+            "1 line, max width 5, 3 comma-separated ints"
+        );
+    }
+}
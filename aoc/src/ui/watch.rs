@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often files are looked at.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A file that was written to, once it stopped changing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Input(String),
+    /// The binary running the TUI was rebuilt.
+    Binary,
+}
+
+/// Polls the modification time of inputs and of the binary from a thread.
+/// The thread stops when the watcher is dropped.
+pub struct Watcher {
+    changes: Receiver<Change>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Watcher {
+    pub fn new(inputs: Vec<String>) -> Self {
+        let (tx, changes) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let mut files = inputs
+            .into_iter()
+            .map(|i| (PathBuf::from(&i), Change::Input(i)))
+            .collect::<Vec<_>>();
+        if let Ok(exe) = std::env::current_exe() {
+            files.push((exe, Change::Binary));
+        }
+
+        let stopped = stop.clone();
+        thread::spawn(move || {
+            let mtime = |path: &PathBuf| fs::metadata(path).and_then(|m| m.modified()).ok();
+
+            let mut seen = files
+                .iter()
+                .map(|(path, _)| (path.clone(), mtime(path)))
+                .collect::<HashMap<_, _>>();
+            // Files that changed since the previous poll, reported once they
+            // are left alone for a poll, so that half-written ones aren't.
+            let mut pending = HashMap::<PathBuf, Option<SystemTime>>::new();
+
+            while !stopped.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+
+                for (path, change) in files.iter() {
+                    let now = mtime(path);
+
+                    match pending.get(path) {
+                        Some(last) if *last == now => {
+                            pending.remove(path);
+                            seen.insert(path.clone(), now);
+                            if now.is_some() && tx.send(change.clone()).is_err() {
+                                return;
+                            }
+                        }
+                        Some(_) => {
+                            pending.insert(path.clone(), now);
+                        }
+                        None if seen.get(path) != Some(&now) => {
+                            pending.insert(path.clone(), now);
+                        }
+                        None => {}
+                    }
+                }
+            }
+        });
+
+        Self { changes, stop }
+    }
+
+    /// The changes noticed since the last call.
+    pub fn changes(&self) -> Vec<Change> {
+        self.changes.try_iter().collect()
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
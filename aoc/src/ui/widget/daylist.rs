@@ -40,14 +40,9 @@ impl DayList {
         }
     }
 
-    pub fn rows(&self, day: &Day) -> Vec<Row> {
+    pub fn rows(&self, day: &Day) -> Vec<Row<'_>> {
         let number = Cell::from(format!("{}", day.number));
-        let title = Cell::from(
-            day.day
-                .as_ref()
-                .map(|d| d.title())
-                .unwrap_or_else(String::new),
-        );
+        let title = Cell::from(day.day.as_ref().map(|d| d.title()).unwrap_or_default());
 
         let status = if day.is_present() {
             Cell::from(format!("{}", day.status())).style(day.status().style())
@@ -103,11 +98,15 @@ impl Widget for DayList {
 
         let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-        let rows = app.days.iter().flat_map(|d| self.rows(d));
+        let rows = app.days().iter().flat_map(|d| self.rows(d));
 
         let table = Table::new(rows)
             .header(header)
-            .block(Block::default().borders(Borders::ALL).title("AoC 2021"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("AoC {}", app.year())),
+            )
             .highlight_style(self.selected_style(app))
             .highlight_symbol("> ")
             .widths(&[
@@ -134,7 +133,7 @@ impl NavigableWidget for DayList {
             &mut self.part_state
         };
 
-        let len = app.days.len();
+        let len = app.days().len();
 
         let mut s = state.selected();
         cycle_up(&mut s, len - 1);
@@ -149,7 +148,7 @@ impl NavigableWidget for DayList {
             &mut self.part_state
         };
 
-        let len = app.days.len();
+        let len = app.days().len();
 
         let mut s = state.selected();
         cycle_down(&mut s, len - 1);
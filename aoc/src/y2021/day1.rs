@@ -0,0 +1,84 @@
+use crate::{aoc, Answer, AocDay, Context, ParseError, Variant};
+
+use itertools::Itertools;
+
+#[aoc(day = 1, title = "Sonar Sweep", inputs("small", "real"))]
+pub struct Day1;
+
+impl Day1 {
+    /// Consecutive windows share two depths, so comparing their sums comes down
+    /// to comparing the depths three apart.
+    fn part_2_offset(
+        &self,
+        input: &<Self as AocDay>::Parsed,
+        _ctx: &Context,
+    ) -> color_eyre::eyre::Result<Answer> {
+        let result = input
+            .iter()
+            .zip(input.iter().skip(3))
+            .filter(|(a, b)| b > a)
+            .count();
+
+        Ok(result.into())
+    }
+}
+
+impl AocDay for Day1 {
+    type Parsed = Vec<usize>;
+
+    fn parse(&self, input: Vec<String>) -> color_eyre::eyre::Result<Self::Parsed> {
+        Ok(input
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| !s.trim().is_empty())
+            .map(|(line, s)| {
+                s.trim().parse::<usize>().map_err(|e| {
+                    ParseError::at_token(line, s, s.trim(), format!("invalid depth: {}", e))
+                })
+            })
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_1(&self, input: &Self::Parsed, _ctx: &Context) -> color_eyre::eyre::Result<Answer> {
+        let result = input
+            .iter()
+            .fold((None, 0), |(a, mut n), b| {
+                if let Some(depth) = a {
+                    if b > depth {
+                        n += 1;
+                    }
+                };
+
+                (Some(b), n)
+            })
+            .1;
+
+        Ok(result.into())
+    }
+
+    fn part_2(&self, input: &Self::Parsed, _ctx: &Context) -> color_eyre::eyre::Result<Answer> {
+        let result = input
+            .iter()
+            .tuple_windows()
+            .map(|(a, b, c)| a + b + c)
+            .fold((None, 0), |(a, mut n), b| {
+                if let Some(depth) = a {
+                    if b > depth {
+                        n += 1;
+                    }
+                };
+
+                (Some(b), n)
+            })
+            .1;
+
+        Ok(result.into())
+    }
+
+    fn variants(&self, part: usize) -> Vec<Variant<Self>> {
+        match part {
+            2 => vec![Variant::new("offset", Self::part_2_offset)],
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{aoc, Answer, AocDay, Context, ParseError};
+
+#[aoc(day = 2, title = "Dive!", inputs("small", "real"))]
+pub struct Day2;
+
+enum CommandKind {
+    Up,
+    Down,
+    Forward,
+}
+
+pub struct Command {
+    kind: CommandKind,
+    amount: isize,
+}
+
+impl Command {
+    fn parse(line: usize, s: &str) -> Result<Self, ParseError> {
+        let mut elements = s.split_ascii_whitespace();
+        let kind = elements
+            .next()
+            .ok_or_else(|| ParseError::new(line, 0, s, "expected a command"))?;
+        let amount = elements.next().ok_or_else(|| {
+            ParseError::new(line, s.chars().count(), s, "expected an amount")
+        })?;
+
+        let kind = match kind {
+            "forward" => CommandKind::Forward,
+            "up" => CommandKind::Up,
+            "down" => CommandKind::Down,
+            k => {
+                return Err(ParseError::at_token(
+                    line,
+                    s,
+                    k,
+                    format!("unknown command `{}`", k),
+                ))
+            }
+        };
+
+        Ok(Command {
+            kind,
+            amount: amount.parse().map_err(|e| {
+                ParseError::at_token(line, s, amount, format!("invalid amount: {}", e))
+            })?,
+        })
+    }
+}
+
+impl AocDay for Day2 {
+    type Parsed = Vec<Command>;
+
+    fn parse(&self, input: Vec<String>) -> color_eyre::eyre::Result<Self::Parsed> {
+        Ok(input
+            .iter()
+            .enumerate()
+            .map(|(line, s)| Command::parse(line, s))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_1(&self, input: &Self::Parsed, _ctx: &Context) -> color_eyre::eyre::Result<Answer> {
+        let (depth, position) = input.iter().fold(
+            (0, 0),
+            |(mut depth, mut position), c: &Command| {
+                match c.kind {
+                    CommandKind::Up => depth -= c.amount,
+                    CommandKind::Down => depth += c.amount,
+                    CommandKind::Forward => position += c.amount,
+                };
+                (depth, position)
+            },
+        );
+
+        Ok((depth * position).into())
+    }
+
+    fn part_2(&self, input: &Self::Parsed, _ctx: &Context) -> color_eyre::eyre::Result<Answer> {
+        let (depth, position, _) = input.iter().fold(
+            (0, 0, 0),
+            |(mut depth, mut position, mut aim), c: &Command| {
+                match c.kind {
+                    CommandKind::Up => aim -= c.amount,
+                    CommandKind::Down => aim += c.amount,
+                    CommandKind::Forward => {
+                        position += c.amount;
+                        depth += c.amount * aim;
+                    }
+                };
+                (depth, position, aim)
+            },
+        );
+
+        Ok((depth * position).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(line: &str) -> ParseError {
+        let input = vec![String::from("forward 5"), line.to_string()];
+        Day2.parse(input)
+            .err()
+            .unwrap()
+            .downcast::<ParseError>()
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = parse_error("");
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.reason, "expected a command");
+
+        let e = parse_error("down");
+        assert_eq!((e.line, e.column), (2, 5));
+        assert_eq!(e.reason, "expected an amount");
+
+        let e = parse_error("  sideways 3");
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.reason, "unknown command `sideways`");
+
+        let e = parse_error("up three");
+        assert_eq!((e.line, e.column), (2, 4));
+        assert_eq!(e.reason, "invalid amount: invalid digit found in string");
+        assert_eq!(e.text, "up three");
+    }
+}
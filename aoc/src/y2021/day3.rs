@@ -0,0 +1,266 @@
+use color_eyre::eyre;
+
+use crate::{aoc, Answer, AocDay, Context, ParseError};
+
+#[aoc(day = 3, title = "Binary Diagnostic", inputs("small", "real"))]
+pub struct Day3;
+
+/// The number of zeros and ones at `bit` among the values.
+fn rate_bits(values: &[Vec<u8>], bit: usize) -> eyre::Result<(usize, usize)> {
+    values.iter().try_fold((0, 0), |(zeros, ones), v| match v.get(bit) {
+        Some(0) => Ok((zeros + 1, ones)),
+        Some(_) => Ok((zeros, ones + 1)),
+        None => Err(eyre::eyre!("A value has no bit {}", bit)),
+    })
+}
+
+enum FilterPredicate {
+    Min,
+    Max,
+}
+
+fn filter_values(
+    values: &[Vec<u8>],
+    rating: (usize, usize),
+    bit: usize,
+    predicate: FilterPredicate,
+) -> Vec<Vec<u8>> {
+    let target = match predicate {
+        FilterPredicate::Min => {
+            if rating.1 < rating.0 {
+                1
+            } else {
+                0
+            }
+        }
+        FilterPredicate::Max => {
+            if rating.0 <= rating.1 {
+                1
+            } else {
+                0
+            }
+        }
+    };
+
+    values
+        .iter()
+        .filter(|v| v.get(bit) == Some(&target))
+        .cloned()
+        .collect()
+}
+
+/// The number written by the bits, most significant first.
+fn to_number(bits: &[u8]) -> eyre::Result<u64> {
+    bits.iter()
+        .try_fold(0u64, |n, b| n.checked_mul(2).map(|n| n + u64::from(*b)))
+        .ok_or_else(|| eyre::eyre!("{} bits don't fit in a 64-bit number", bits.len()))
+}
+
+impl AocDay for Day3 {
+    type Parsed = Vec<Vec<u8>>;
+
+    fn parse(&self, input: Vec<String>) -> color_eyre::eyre::Result<Self::Parsed> {
+        let width = match input.first() {
+            Some(first) if !first.is_empty() => first.chars().count(),
+            _ => return Err(ParseError::new(0, 0, "", "expected binary numbers").into()),
+        };
+
+        Ok(input
+            .iter()
+            .enumerate()
+            .map(|(line, s)| {
+                let bits = s
+                    .chars()
+                    .enumerate()
+                    .map(|(column, i)| match i {
+                        '0' => Ok(0),
+                        '1' => Ok(1),
+                        c => Err(ParseError::new(
+                            line,
+                            column,
+                            s.as_str(),
+                            format!("expected a binary digit, found `{}`", c),
+                        )),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if bits.len() != width {
+                    return Err(ParseError::new(
+                        line,
+                        bits.len().min(width),
+                        s.as_str(),
+                        format!("expected {} bits as on line 1, found {}", width, bits.len()),
+                    ));
+                }
+                Ok(bits)
+            })
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn part_1(&self, input: &Self::Parsed, _ctx: &Context) -> color_eyre::eyre::Result<Answer> {
+        let len = input
+            .first()
+            .map(Vec::len)
+            .ok_or_else(|| eyre::eyre!("There are no binary numbers to rate"))?;
+
+        let result = input
+            .iter()
+            .map(|i| {
+                i.iter()
+                    .map(|b| if *b == 0 { (1, 0) } else { (0, 1) })
+                    .collect::<Vec<_>>()
+            })
+            .fold(Vec::new(), |mut vec, bits| {
+                if vec.is_empty() {
+                    vec = bits
+                } else {
+                    vec.iter_mut().zip(bits.iter()).for_each(|(a, b)| {
+                        a.0 += b.0;
+                        a.1 += b.1;
+                    });
+                }
+                vec
+            });
+
+        let mask = 64usize
+            .checked_sub(len)
+            .and_then(|shift| u64::MAX.checked_shr(shift as u32))
+            .ok_or_else(|| eyre::eyre!("Numbers of {} bits can't be rated", len))?;
+
+        let gamma = to_number(
+            &result
+                .iter()
+                .map(|c| if c.0 > c.1 { 0 } else { 1 })
+                .collect::<Vec<_>>(),
+        )?;
+
+        let epsilon = (!gamma) & mask;
+
+        // Both fit in 64 bits, so their product fits in an `i128`.
+        Ok((i128::from(gamma) * i128::from(epsilon)).into())
+    }
+
+    fn part_2(&self, input: &Self::Parsed, ctx: &Context) -> color_eyre::eyre::Result<Answer> {
+        let mut v_max = input.clone();
+        let mut v_min = v_max.clone();
+
+        let width = v_max.first().map_or(0, Vec::len);
+
+        for bit in 0..=width {
+            ctx.check_cancelled()?;
+            ctx.progress(bit, width);
+
+            match (v_max.len(), v_min.len()) {
+                (x, y) if x == 0 || y == 0 => {
+                    return Err(eyre::eyre!("No number is left to rate at bit {}", bit))
+                }
+                (1, 1) => break,
+                _ if bit == width => {
+                    return Err(eyre::eyre!(
+                        "Several numbers are left after all {} bits",
+                        width
+                    ))
+                }
+                (1, _) => {
+                    v_min =
+                        filter_values(&v_min, rate_bits(&v_min, bit)?, bit, FilterPredicate::Min);
+                }
+                (_, 1) => {
+                    v_max =
+                        filter_values(&v_max, rate_bits(&v_max, bit)?, bit, FilterPredicate::Max);
+                }
+                (_, _) => {
+                    v_min =
+                        filter_values(&v_min, rate_bits(&v_min, bit)?, bit, FilterPredicate::Min);
+                    v_max =
+                        filter_values(&v_max, rate_bits(&v_max, bit)?, bit, FilterPredicate::Max);
+                }
+            }
+            // self.println(format!("v_max: {:?}\nv_min:{:?}\n", v_max, v_min), &ctx.debug);
+        }
+
+        // The loop only ends with one number left on each side.
+        let oxy = to_number(&v_max[0])?;
+        let co2 = to_number(&v_min[0])?;
+
+        self.println(format!("oxygen: {}, co2: {}", oxy, co2), &ctx.output);
+
+        let rating = u128::from(oxy) * u128::from(co2);
+        let rating = i128::try_from(rating)
+            .map_err(|_| eyre::eyre!("The life support rating {} is too large", rating))?;
+
+        Ok(rating.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &[&str]) -> ParseError {
+        let input = input.iter().map(|s| s.to_string()).collect();
+        Day3.parse(input)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap()
+    }
+
+    fn run(part: usize, input: &[&str]) -> color_eyre::eyre::Result<Answer> {
+        let input = input.iter().map(|s| s.to_string()).collect();
+        let parsed = Day3.parse(input)?;
+        let (output, _output_rx) = std::sync::mpsc::channel();
+        let (debug, _debug_rx) = std::sync::mpsc::channel();
+        let ctx = Context::new(output, debug);
+
+        match part {
+            1 => Day3.part_1(&parsed, &ctx),
+            _ => Day3.part_2(&parsed, &ctx),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_empty_input() {
+        let e = parse_error(&[]);
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.reason, "expected binary numbers");
+
+        assert_eq!(parse_error(&["", "101"]).reason, "expected binary numbers");
+    }
+
+    #[test]
+    fn parse_rejects_other_digits() {
+        let e = parse_error(&["00100", "11210"]);
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(e.reason, "expected a binary digit, found `2`");
+    }
+
+    #[test]
+    fn parse_rejects_other_widths() {
+        let e = parse_error(&["00100", "1111"]);
+        assert_eq!((e.line, e.column), (2, 5));
+        assert_eq!(e.reason, "expected 5 bits as on line 1, found 4");
+
+        let e = parse_error(&["00100", "111101"]);
+        assert_eq!((e.line, e.column), (2, 6));
+        assert_eq!(e.text, "111101");
+    }
+
+    #[test]
+    fn large_numbers_are_errors() {
+        let wide = "1".repeat(65);
+        assert!(run(1, &[&wide]).is_err());
+        assert!(run(2, &[&wide, &wide]).is_err());
+
+        let full = "10".repeat(32);
+        assert_eq!(
+            run(1, &[&full]).unwrap(),
+            Answer::Integer(0xAAAA_AAAA_AAAA_AAAA * 0x5555_5555_5555_5555)
+        );
+    }
+
+    #[test]
+    fn ratings_without_a_single_number_are_errors() {
+        assert!(run(2, &["101", "101"]).is_err());
+        assert!(run(2, &["111", "110", "111"]).is_err());
+    }
+}
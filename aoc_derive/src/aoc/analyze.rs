@@ -0,0 +1,92 @@
+use proc_macro_error::{abort, abort_call_site};
+use syn::spanned::Spanned;
+use syn::{ItemStruct, Lit, LitStr, Meta, NestedMeta};
+
+use super::parse::Ast;
+
+pub struct Model {
+    pub day: usize,
+    pub title: LitStr,
+    pub inputs: Vec<LitStr>,
+    pub item: ItemStruct,
+}
+
+fn string(lit: &Lit) -> LitStr {
+    match lit {
+        Lit::Str(s) => s.clone(),
+        lit => abort!(lit, "Expected a string"),
+    }
+}
+
+pub fn analyze(ast: Ast) -> Model {
+    let mut day = None;
+    let mut title = None;
+    let mut inputs = None;
+
+    for arg in ast.args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("day") => {
+                if day.is_some() {
+                    abort!(nv, "The day is given more than once");
+                }
+                let n = match &nv.lit {
+                    Lit::Int(n) => n.base10_parse().ok().filter(|n| (1..=25).contains(n)),
+                    _ => None,
+                };
+                match n {
+                    Some(n) => day = Some(n),
+                    None => abort!(nv.lit, "The day number must be between 1 and 25 included."),
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("title") => {
+                if title.is_some() {
+                    abort!(nv, "The title is given more than once");
+                }
+                title = Some(string(&nv.lit));
+            }
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("inputs") => {
+                if inputs.is_some() {
+                    abort!(list, "The inputs are given more than once");
+                }
+                let names = list
+                    .nested
+                    .iter()
+                    .map(|n| match n {
+                        NestedMeta::Lit(lit) => string(lit),
+                        n => abort!(n, "Expected the name of an input, such as \"small\""),
+                    })
+                    .collect::<Vec<_>>();
+
+                if names.is_empty() {
+                    abort!(list, "A day needs at least one input");
+                }
+                inputs = Some(names);
+            }
+            arg => abort!(
+                arg,
+                "Unknown argument";
+                help = "#[aoc] takes `day = N`, `title = \"...\"` and `inputs(\"...\", ...)`"
+            ),
+        }
+    }
+
+    if !ast.item.generics.params.is_empty() {
+        abort!(ast.item.generics.span(), "Days can't be generic");
+    }
+
+    Model {
+        day: day.unwrap_or_else(|| {
+            abort_call_site!("#[aoc] needs the number of the day, as `day = N`")
+        }),
+        title: title.unwrap_or_else(|| {
+            abort_call_site!("#[aoc] needs the title of the day, as `title = \"...\"`")
+        }),
+        inputs: inputs.unwrap_or_else(|| {
+            vec![
+                LitStr::new("small", ast.item.ident.span()),
+                LitStr::new("real", ast.item.ident.span()),
+            ]
+        }),
+        item: ast.item,
+    }
+}
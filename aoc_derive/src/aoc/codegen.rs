@@ -0,0 +1,104 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Fields;
+
+use super::analyze::Model;
+
+fn gen_default(model: &Model) -> TokenStream {
+    let ident = &model.item.ident;
+
+    let body = match &model.item.fields {
+        Fields::Unit => quote! { Self },
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote! { Self { #(#names: Default::default(),)* } }
+        }
+        Fields::Unnamed(fields) => {
+            let defaults = fields.unnamed.iter().map(|_| quote! { Default::default() });
+            quote! { Self(#(#defaults),*) }
+        }
+    };
+
+    quote! {
+        impl Default for #ident {
+            fn default() -> Self {
+                #body
+            }
+        }
+    }
+}
+
+/// One test per input and part, checking the answers of every variant. Tests
+/// of inputs that aren't on disk, or have no expected answer, pass.
+fn gen_tests(model: &Model) -> TokenStream {
+    let ident = &model.item.ident;
+
+    let tests = model.inputs.iter().enumerate().flat_map(|(index, input)| {
+        let name = input
+            .value()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+
+        (1..=2usize).map(move |part| {
+            let name = format_ident!("{}_part{}", name, part);
+
+            quote! {
+                #[test]
+                fn #name() {
+                    crate::check_expected_answer(&super::#ident::default(), #index, #part);
+                }
+            }
+        })
+    });
+
+    quote! {
+        #[cfg(test)]
+        mod expected_answers {
+            #(#tests)*
+        }
+    }
+}
+
+pub fn codegen(model: Model) -> TokenStream {
+    let item = &model.item;
+    let ident = &item.ident;
+    let day = model.day;
+    let title = &model.title;
+    let inputs = &model.inputs;
+    let default = gen_default(&model);
+    let tests = gen_tests(&model);
+
+    quote! {
+        #item
+
+        #default
+
+        impl crate::DayInfo for #ident {
+            const YEAR: u16 = super::YEAR;
+            const DAY: usize = #day;
+            const TITLE: &'static str = #title;
+
+            fn input_paths() -> &'static [&'static str] {
+                static PATHS: std::sync::OnceLock<Vec<&'static str>> = std::sync::OnceLock::new();
+
+                PATHS.get_or_init(|| {
+                    [#(#inputs),*]
+                        .iter()
+                        .map(|name| {
+                            let path =
+                                crate::fetch::InputManager::input_path(Self::YEAR, Self::DAY, name);
+                            &*Box::leak(path.to_string_lossy().into_owned().into_boxed_str())
+                        })
+                        .collect()
+                })
+            }
+        }
+
+        /// The day of this module, for `days!` to find it by.
+        #[doc(hidden)]
+        pub type __AocDay = #ident;
+
+        #tests
+    }
+}
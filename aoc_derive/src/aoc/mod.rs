@@ -0,0 +1,16 @@
+use proc_macro::TokenStream;
+
+mod parse;
+pub use parse::parse;
+
+mod analyze;
+pub use analyze::analyze;
+
+mod codegen;
+pub use codegen::codegen;
+
+pub fn aoc_impl(args: TokenStream, item: TokenStream) -> TokenStream {
+    let ast = parse(args.into(), item.into());
+    let model = analyze(ast);
+    codegen(model).into()
+}
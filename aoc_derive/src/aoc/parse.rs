@@ -0,0 +1,26 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{ItemStruct, NestedMeta, Token};
+
+pub struct Ast {
+    pub args: Vec<NestedMeta>,
+    pub item: ItemStruct,
+}
+
+pub fn parse(args: TokenStream, item: TokenStream) -> Ast {
+    let args = match Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse2(args) {
+        Ok(args) => args.into_iter().collect(),
+        Err(e) => {
+            abort!(e.span(), "{}", e; help = "Expected #[aoc(day = 3, title = \"...\", inputs(\"small\", \"real\"))]")
+        }
+    };
+
+    let item = match syn::parse2(item) {
+        Ok(item) => item,
+        Err(e) => abort!(e.span(), "#[aoc] goes on the struct of a day"),
+    };
+
+    Ast { args, item }
+}
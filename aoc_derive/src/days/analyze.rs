@@ -1,17 +1,155 @@
-use proc_macro_error::abort_call_site;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
 
-use super::parse::Ast;
+use proc_macro2::Span;
+use proc_macro_error::{abort, abort_call_site, emit_error};
 
-pub type Model = usize;
+use super::parse::{Ast, DayItem, Days};
+
+/// The days implemented for a year, in order.
+pub struct Year {
+    pub year: u16,
+    pub days: Vec<usize>,
+}
+
+pub type Model = Vec<Year>;
+
+/// The directory holding the `dayN.rs` files of a year.
+fn year_dir(year: u16) -> PathBuf {
+    let root = env::var("CARGO_MANIFEST_DIR")
+        .unwrap_or_else(|_| abort_call_site!("days! must be built by cargo"));
+
+    PathBuf::from(root).join("src").join(format!("y{}", year))
+}
+
+fn day_exists(year: u16, day: usize) -> bool {
+    let dir = year_dir(year);
+    dir.join(format!("day{}.rs", day)).is_file()
+        || dir.join(format!("day{}", day)).join("mod.rs").is_file()
+}
+
+fn missing_file(year: u16, day: usize, span: Span) {
+    emit_error!(
+        span,
+        "There is no file for day {}", day;
+        help = "Create src/y{}/day{}.rs", year, day
+    );
+}
+
+fn day_number(n: &syn::LitInt) -> usize {
+    match n.base10_parse() {
+        Ok(n) if (1..=25).contains(&n) => n,
+        _ => abort!(n, "The day number must be between 1 and 25 included."),
+    }
+}
+
+/// Every `dayN.rs` file, or `dayN/mod.rs`, of a year.
+fn discover(year: u16, span: Span) -> Vec<usize> {
+    let dir = year_dir(year);
+    let entries = fs::read_dir(&dir)
+        .unwrap_or_else(|e| abort!(span, "Could not read {}: {}", dir.display(), e));
+
+    let mut days = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let n = name.strip_prefix("day")?;
+            let n = n.strip_suffix(".rs").unwrap_or(n);
+            n.parse::<usize>().ok()
+        })
+        .filter(|n| (1..=25).contains(n) && day_exists(year, *n))
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        abort!(span, "No dayN.rs file in {}", dir.display());
+    }
+
+    days.sort_unstable();
+    days
+}
+
+/// The days of an explicit list, checking each is given once and has a file.
+fn list(year: u16, items: &[DayItem]) -> Vec<usize> {
+    let mut days: Vec<usize> = Vec::new();
+
+    for item in items {
+        let (numbers, span) = match item {
+            DayItem::Single(n) => (vec![day_number(n)], n.span()),
+            DayItem::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let first = day_number(start);
+                let last = if *inclusive {
+                    day_number(end)
+                } else {
+                    day_number(end).saturating_sub(1)
+                };
+
+                if first > last {
+                    abort!(item.span(), "This range of days is empty");
+                }
+
+                ((first..=last).collect(), item.span())
+            }
+        };
+
+        for n in numbers {
+            if days.contains(&n) {
+                emit_error!(span, "Day {} is listed more than once", n);
+            } else if !day_exists(year, n) {
+                missing_file(year, n, span);
+            } else {
+                days.push(n);
+            }
+        }
+    }
+
+    days.sort_unstable();
+    days
+}
 
 pub fn analyze(ast: Ast) -> Model {
-    if let Ok(n) = ast.base10_parse() {
-        if (1..=25).contains(&n) {
-            n
-        } else {
-            abort_call_site!("The day number must be between 1 and 25 included.")
+    if ast.is_empty() {
+        abort_call_site!("days! needs at least one year");
+    }
+
+    let mut model: Model = Vec::with_capacity(ast.len());
+
+    for entry in ast {
+        let year = match entry.year.base10_parse::<u16>() {
+            Ok(y) if y >= 2015 => y,
+            _ => abort!(entry.year, "Advent of Code started in 2015"),
+        };
+
+        if model.iter().any(|y| y.year == year) {
+            abort!(entry.year, "Year {} is listed more than once", year);
         }
-    } else {
-        abort_call_site!("Wrong number literal somehow");
+
+        let days = match &entry.days {
+            Days::UpTo(n) => (1..=day_number(n))
+                .filter(|d| {
+                    let exists = day_exists(year, *d);
+                    if !exists {
+                        missing_file(year, *d, n.span());
+                    }
+                    exists
+                })
+                .collect(),
+            Days::List(span, items) => {
+                if items.is_empty() {
+                    abort!(*span, "The list of days is empty");
+                }
+                list(year, items)
+            }
+            Days::Discover(span) => discover(year, *span),
+        };
+
+        model.push(Year { year, days });
     }
+
+    model.sort_by_key(|y| y.year);
+    model
 }
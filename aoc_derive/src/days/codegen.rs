@@ -1,4 +1,4 @@
-use super::analyze::Model;
+use super::analyze::{Model, Year};
 use proc_macro2::{Span, TokenStream};
 use syn::Ident;
 
@@ -11,36 +11,86 @@ where
     Ident::new(s.as_ref(), Span::call_site())
 }
 
+fn year_mod(year: &Year) -> Ident {
+    ident(format!("y{}", year.year))
+}
+
+fn gen_mods(model: &Model) -> TokenStream {
+    let mods = model.iter().map(|year| {
+        let year_mod = year_mod(year);
+        let number = year.year;
+        let days_mod = year
+            .days
+            .iter()
+            .map(|i| ident(format!("day{}", i)))
+            .collect::<Vec<_>>();
+
+        // Each day must be declared with `#[aoc]`, with its own number.
+        let checks = year.days.iter().zip(days_mod.iter()).map(|(i, day_mod)| {
+            let message = format!(
+                "{}/{}.rs must be declared with #[aoc(day = {})]",
+                year_mod, day_mod, i
+            );
+            quote! {
+                const _: () = assert!(<#day_mod::__AocDay as crate::DayInfo>::DAY == #i, #message);
+            }
+        });
+
+        quote! {
+            pub mod #year_mod {
+                pub const YEAR: u16 = #number;
+
+                #(pub mod #days_mod; )*
+                #(pub use #days_mod::*;)*
+
+                #(#checks)*
+            }
+        }
+    });
+
+    quote! {
+        #(#mods)*
+    }
+}
+
 fn gen_get(model: &Model) -> TokenStream {
-    let days_struct = (1..=*model)
-        .map(|i| ident(format!("Day{}", i)))
-        .collect::<Vec<_>>();
+    let arms = model.iter().flat_map(|year| {
+        let year_mod = year_mod(year);
+        let number = year.year;
 
-    let days = (1..=*model).collect::<Vec<_>>();
+        year.days.iter().map(move |i| {
+            let day_mod = ident(format!("day{}", i));
+            quote! {
+                (#number, #i) => Ok(Box::new(#year_mod::#day_mod::__AocDay::default())),
+            }
+        })
+    });
+
+    let years = model.iter().map(|y| y.year);
 
     quote! {
-        pub fn get_day(day: usize) -> eyre::Result<Box<dyn AocDay + Send + Sync>> {
-            let error: eyre::Result<Box<dyn AocDay + Send + Sync>> = match day {
-                #(#days => Ok(Box::new(#days_struct::default())),)*
-                x if (1..=25).contains(&x) => Err(eyre::eyre!(AocError::UnimplementedDay)),
-                x => Err(eyre::eyre!(AocError::NonExistentDay)).suggestion("AoC runs from the 1st to the 25th; try using one of these days."),
+        /// The years with at least one implemented day, in order.
+        pub const YEARS: &[u16] = &[#(#years),*];
+
+        pub fn get_day(year: u16, day: usize) -> eyre::Result<Box<dyn DynDay + Send + Sync>> {
+            let error: eyre::Result<Box<dyn DynDay + Send + Sync>> = match (year, day) {
+                #(#arms)*
+                (y, _) if !YEARS.contains(&y) => Err(eyre::eyre!(AocError::UnimplementedYear)).suggestion(format!("Try one of the implemented years: {:?}", YEARS)),
+                (_, x) if (1..=25).contains(&x) => Err(eyre::eyre!(AocError::UnimplementedDay)),
+                _ => Err(eyre::eyre!(AocError::NonExistentDay)).suggestion("AoC runs from the 1st to the 25th; try using one of these days."),
             };
 
-            error.wrap_err_with(|| format!("Failed to build AoC day {}", day))
+            error.wrap_err_with(|| format!("Failed to build AoC {} day {}", year, day))
         }
     }
 }
 
 pub fn codegen(model: Model) -> TokenStream {
-    let days_mod = (1..=model)
-        .map(|i| ident(format!("day{}", i)))
-        .collect::<Vec<_>>();
-
+    let mods = gen_mods(&model);
     let get = gen_get(&model);
 
     quote! {
-        #(mod #days_mod; )*
-        #(pub use #days_mod::*;)*
+        #mods
 
         #get
     }
@@ -1,15 +1,110 @@
-use proc_macro2::TokenStream;
-use proc_macro_error::abort_call_site;
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::{abort, abort_call_site};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{bracketed, LitInt, Token};
 
-pub type Ast = syn::LitInt;
+/// A day, or a range of days, in an explicit list.
+pub enum DayItem {
+    Single(LitInt),
+    /// `start..end`, or `start..=end` when inclusive.
+    Range {
+        start: LitInt,
+        end: LitInt,
+        inclusive: bool,
+    },
+}
+
+impl DayItem {
+    pub fn span(&self) -> Span {
+        match self {
+            DayItem::Single(n) => n.span(),
+            DayItem::Range { start, end, .. } => start
+                .span()
+                .join(end.span())
+                .unwrap_or_else(|| start.span()),
+        }
+    }
+}
+
+impl Parse for DayItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let start = input.parse()?;
+
+        if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            Ok(DayItem::Range {
+                start,
+                end: input.parse()?,
+                inclusive: true,
+            })
+        } else if input.peek(Token![..]) {
+            input.parse::<Token![..]>()?;
+            Ok(DayItem::Range {
+                start,
+                end: input.parse()?,
+                inclusive: false,
+            })
+        } else {
+            Ok(DayItem::Single(start))
+        }
+    }
+}
+
+/// The days of a year.
+pub enum Days {
+    /// `N`: every day from 1 to N.
+    UpTo(LitInt),
+    /// `[1, 2, 7, 10..=12]`.
+    List(Span, Vec<DayItem>),
+    /// `*`: every `dayN.rs` file in the directory of the year.
+    Discover(Span),
+}
+
+impl Parse for Days {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![*]) {
+            let star = input.parse::<Token![*]>()?;
+            Ok(Days::Discover(star.span()))
+        } else if input.peek(syn::token::Bracket) {
+            let content;
+            let brackets = bracketed!(content in input);
+            let items = Punctuated::<DayItem, Token![,]>::parse_terminated(&content)?;
+            Ok(Days::List(brackets.span, items.into_iter().collect()))
+        } else {
+            Ok(Days::UpTo(input.parse()?))
+        }
+    }
+}
+
+/// One `year => days` entry.
+pub struct YearDays {
+    pub year: LitInt,
+    pub days: Days,
+}
+
+impl Parse for YearDays {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let year = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let days = input.parse()?;
+
+        Ok(Self { year, days })
+    }
+}
+
+pub type Ast = Vec<YearDays>;
 
 pub fn parse(args: TokenStream) -> Ast {
-    const MESSAGE: &str = "days! takes a single integer as an argument";
+    const MESSAGE: &str = "days! takes a list of `year => days`, where days are a count, a list such as `[1, 2, 7, 10..=12]`, or `*` to find them";
     if args.is_empty() {
         abort_call_site!(MESSAGE)
-    } else if let Ok(ast) = syn::parse2(args) {
-        ast
     } else {
-        abort_call_site!(MESSAGE)
+        match syn::parse::Parser::parse2(Punctuated::<YearDays, Token![,]>::parse_terminated, args)
+        {
+            Ok(ast) => ast.into_iter().collect(),
+            Err(e) => abort!(e.span(), "{}", e; help = MESSAGE),
+        }
     }
 }
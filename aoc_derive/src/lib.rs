@@ -1,10 +1,36 @@
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
 
+mod aoc;
 mod days;
 
+/// Declares the days of each year, found in `src/y<year>/day<N>.rs`:
+///
+/// ```ignore
+/// days!(2020 => 25, 2021 => [1, 2, 3, 7, 10..=12], 2022 => *);
+/// ```
+///
+/// A count means every day up to it, and `*` every `dayN.rs` file of the
+/// year. Cargo doesn't know that new files matter to `*`, so the crate needs a
+/// build script watching `src/` for them to be picked up.
 #[proc_macro]
 #[proc_macro_error]
 pub fn days(args: TokenStream) -> TokenStream {
     days::days_impl(args)
 }
+
+/// Declares the metadata of a day on its struct, and makes it known to
+/// `days!`:
+///
+/// ```ignore
+/// #[aoc(day = 3, title = "Binary Diagnostic", inputs("small", "real"))]
+/// pub struct Day3;
+/// ```
+///
+/// Inputs are named after their file in `inputs/<year>/day<N>/`, and default
+/// to `small` and `real`. The struct also gets a `Default` implementation.
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    aoc::aoc_impl(args, item)
+}